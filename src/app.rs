@@ -1,9 +1,12 @@
 // src/app.rs
-use sysinfo::{System};
+use sysinfo::System;
+use std::any::Any;
 use std::io;
+use std::sync::mpsc::{Receiver, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::{Duration, Instant};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -11,59 +14,222 @@ use ratatui::{
     backend::{CrosstermBackend},
     Terminal,
 };
+use clap::ArgMatches;
 use crate::ui::draw;
-use crate::data::{Cores, Memory}; // 新增 Memory 模块
+use crate::data::collector::{Collector, Sampled, DEFAULT_HISTORY, DEFAULT_INTERVAL};
+use crate::data::format_duration;
+use crate::data::{Connections, Cores, CpuTimes, DiskInfo, DiskIo, HostInfo, Interrupts, Memory, Pressure, Processes, Sensors, Summary, VmStat}; // 新增 Memory 模块
 use crate::data::net::NetInfo; // 新增 NetInfo 模块
-pub struct SystemInfo {
-    pub cores: Cores, // 改为 pub，方便 DrawUi 访问
-    //后期可以添加更多，比如内存、GPU等
-    pub memory: Memory, // 新增 Memory 字段
-    pub net: NetInfo, // 新增 NetInfo 字段
-    pub sys: System, // 新增 System 字段
-}
+use crate::modules::{self, Module, MODULES};
+use crate::sampler::{self, Control, ReaderTask, Sampler, Task};
+/// 所有采集器只在这里登记一次：SystemInfo 的字段、构造、collectors() / collectors_mut() 和采样线程的读取端都由它生成，
+/// 新增采集器时不会漏掉其中某一处。|system| 是构造时可以使用的 sysinfo System
+macro_rules! system_info {
+    (|$system:ident| $($(#[$doc:meta])* $field:ident: $ty:ty = $new:expr,)*) => {
+        pub struct SystemInfo {
+            pub host: HostInfo, // 主机名、系统版本等静态信息
+            $($(#[$doc])* pub $field: $ty,)*
+        }
 
-pub struct App {
-    pub system_info: SystemInfo, // 改为 pub
-    pub show_cpu: bool,
-    pub show_memory: bool,
-    pub show_gpu: bool,
-    pub show_net: bool,
+        impl SystemInfo {
+            /// system 需要已经刷新过 CPU 列表和内存总量（见 Sampler::new）
+            pub fn new($system: &System) -> SystemInfo {
+                SystemInfo { host: HostInfo::new(), $($field: $new,)* }
+            }
+
+            /// 所有采集器，顺序与 collectors() 相同
+            pub fn collectors_mut(&mut self) -> Vec<&mut dyn Collector> {
+                vec![$(&mut self.$field),*]
+            }
+
+            /// 所有采集器，按登记的顺序
+            pub fn collectors(&self) -> Vec<&dyn Collector> {
+                vec![$(&self.$field),*]
+            }
 
+            /// 为 names 中的采集器创建采样线程中的读取端
+            pub fn tasks(&self, names: &[&str]) -> Vec<Box<dyn Task>> {
+                let mut tasks: Vec<Box<dyn Task>> = Vec::new();
+                $(
+                    if names.contains(&self.$field.name()) {
+                        tasks.push(Box::new(ReaderTask {
+                            name: self.$field.name(),
+                            reader: self.$field.reader(),
+                            apply: |info: &mut SystemInfo, reading, at| info.$field.apply(reading, at),
+                        }));
+                    }
+                )*
+                tasks
+            }
+        }
+    };
 }
 
-impl SystemInfo {
-    pub fn new() -> SystemInfo {
-        let cores = Cores::new();
-        let memory = Memory::new(); // 初始化 Memory
-        let mut net = NetInfo::new(); // 初始化 NetInfo
-        let mut system = System::new(); // <--- 新增：创建 System 实例
-        system.refresh_all(); // <--- 新增：初始化时刷新所有信息
-        net.networks.refresh(true);
-        net.initialize();
-        SystemInfo {cores, memory, net, sys: system}
+system_info! {
+    |system|
+    /// CPU 使用率和频率
+    cores: Cores = Cores::new(system),
+    /// /proc/stat 的 CPU 时间分解
+    cpu_times: CpuTimes = CpuTimes::new(),
+    /// 内存明细
+    memory: Memory = Memory::new(system),
+    /// 分页、交换和缺页速率
+    vmstat: VmStat = VmStat::new(),
+    /// PSI 资源压力
+    pressure: Pressure = Pressure::new(),
+    /// 网络接口流量
+    net: NetInfo = NetInfo::new(),
+    /// TCP/UDP 套接字
+    connections: Connections = Connections::new(),
+    /// 温度传感器
+    sensors: Sensors = Sensors::new(),
+    /// 平均负载、运行队列和进程数量
+    summary: Summary = Summary::new(),
+    /// 中断和上下文切换速率
+    interrupts: Interrupts = Interrupts::new(),
+    /// 磁盘空间
+    disk: DiskInfo = DiskInfo::new(),
+    /// 磁盘 I/O 速率
+    disk_io: DiskIo = DiskIo::new(),
+    /// 进程列表
+    processes: Processes = Processes::new(),
+}
+
+/// 运行时按 '[' / ']' 依次切换的采样间隔
+const INTERVAL_STEPS: [Duration; 7] = [
+    Duration::from_millis(100),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+];
+/// 运行时按 '{' / '}' 依次切换的历史长度
+const HISTORY_STEPS: [Duration; 7] = [
+    Duration::from_secs(30),
+    Duration::from_secs(60),
+    Duration::from_secs(2 * 60),
+    Duration::from_secs(5 * 60),
+    Duration::from_secs(10 * 60),
+    Duration::from_secs(30 * 60),
+    Duration::from_secs(60 * 60),
+];
+
+/// 在 steps 中取比 current 长（longer）或短的下一档；已经是最长或最短时保持不变
+fn step(steps: &[Duration], current: Duration, longer: bool) -> Duration {
+    if longer {
+        steps.iter().copied().find(|&d| d > current).unwrap_or(current)
+    } else {
+        steps.iter().copied().rev().find(|&d| d < current).unwrap_or(current)
     }
 }
 
+pub struct App {
+    pub system_info: Arc<Mutex<SystemInfo>>, // 采样线程写入，界面绘制时读取
+    pub modules: Vec<&'static str>, // 启用的模块（modules::MODULES 中的名称），按注册表的顺序
+    states: Vec<Box<dyn Any>>, // 每个模块的界面状态（Module::state），按注册表的顺序
+    pub status_message: Option<String>, // 最近一次操作的结果，显示在底部提示栏
+    pub interval: Duration, // 采样间隔，修改时用 set_timing
+    pub history: Duration,  // 所有图表显示的时间长度，修改时用 set_timing
+    pub last_sample: Option<Instant>, // 采样线程最近一次完成采样的时间，启动采样线程时为启动时间
+    sampler: Option<Sampler>, // 启动采样线程前由 App 持有
+    control: Option<Sender<Control>>, // 启动采样线程后用来修改采样间隔和采集器
+    collectors: Vec<&'static str>, // 采样线程当前刷新的采集器
+}
+
 impl App {
-    pub fn new(show_cpu: bool, show_memory: bool, show_gpu: bool, show_net: bool) -> App {
+    /// 只显示指定的模块；没有选择任何模块时显示 CPU
+    pub fn new(modules: Vec<&'static str>) -> App {
+        let mut modules: Vec<&'static str> =
+            MODULES.iter().map(|module| module.name).filter(|name| modules.contains(name)).collect();
+        if modules.is_empty() {
+            modules.push("cpu");
+        }
+        let sampler = Sampler::new();
+        let system_info = SystemInfo::new(sampler.system());
+        modules::check_collectors(&system_info);
         App {
-            system_info: SystemInfo::new(),
-
-            show_cpu,
-            show_memory,
-            show_gpu,
-            show_net,
+            system_info: Arc::new(Mutex::new(system_info)),
+            modules,
+            states: MODULES.iter().map(|module| (module.state)()).collect(),
+            status_message: None,
+            interval: DEFAULT_INTERVAL,
+            history: DEFAULT_HISTORY,
+            last_sample: None,
+            sampler: Some(sampler),
+            control: None,
+            collectors: Vec::new(),
         }
     }
     pub fn default() -> App {
-        App {
-            system_info: SystemInfo::new(),
+        App::new(MODULES.iter().map(|module| module.name).collect())
+    }
+
+    /// 模块的界面状态；T 不是任何模块的状态类型时 panic
+    pub fn state<T: 'static>(&self) -> &T {
+        self.states.iter().find_map(|state| state.downcast_ref()).expect("no module has this state type")
+    }
+
+    /// 修改模块的界面状态
+    pub fn state_mut<T: 'static>(&mut self) -> &mut T {
+        self.states.iter_mut().find_map(|state| state.downcast_mut()).expect("no module has this state type")
+    }
+
+    /// 把命令行中各模块自己的参数交给模块处理（Module::configure），在启动采样线程之前调用
+    pub fn configure(&mut self, matches: &ArgMatches) {
+        for module in MODULES {
+            (module.configure)(self, matches);
+        }
+    }
+
+    /// 启用的模块，按注册表的顺序
+    pub fn enabled_modules(&self) -> impl Iterator<Item = &'static Module> + '_ {
+        self.modules.iter().filter_map(|name| modules::find(name))
+    }
+
+    /// 锁住采集器读取或修改数据；采样线程只在写入一轮的结果时持有锁，读取文件时不持有
+    pub fn info(&self) -> MutexGuard<'_, SystemInfo> {
+        sampler::lock(&self.system_info)
+    }
+
+    /// 采样线程超过 4 个采样间隔没有新数据时，返回最近一次采样距今的时间
+    pub fn stale_for(&self) -> Option<Duration> {
+        let age = self.last_sample?.elapsed();
+        (age > self.interval * 4).then_some(age)
+    }
+
+    /// 修改采样间隔和历史长度：所有采集器保留同样时长的历史
+    pub fn set_timing(&mut self, interval: Duration, history: Duration) {
+        self.interval = interval;
+        self.history = history.max(interval);
+        for collector in self.info().collectors_mut() {
+            collector.set_history(self.history);
+        }
+        if let Some(sender) = &self.control {
+            // 采样线程已经退出时发送失败，界面会显示数据过时
+            let _ = sender.send(Control::Interval(interval));
+        }
+    }
+
+    /// 启用的模块在当前视图下需要刷新的采集器。所有采集器共用采样线程中的一个 System，
+    /// 每轮只刷新这些采集器需要的部分（见 Refresh）
+    fn active_collectors(&self) -> Vec<&'static str> {
+        self.enabled_modules()
+            .flat_map(|module| module.collectors.iter().copied().filter(|name| (module.needs)(self, name)))
+            .collect()
+    }
 
-            show_cpu: true,
-            show_memory: true,
-            show_gpu: true,
-            show_net: true,
+    /// 切换视图后通知采样线程开始或停止读取对应的采集器
+    fn update_collectors(&mut self) {
+        let collectors = self.active_collectors();
+        if collectors == self.collectors {
+            return;
         }
+        if let Some(sender) = &self.control {
+            let _ = sender.send(Control::Collectors(collectors.clone()));
+        }
+        self.collectors = collectors;
     }
 
     pub fn run(&mut self) -> io::Result<()> {
@@ -89,41 +255,112 @@ impl App {
 
         Ok(())
     }
-   // 一个更完整的主循环
+    /// 启动采样线程，返回每轮采样完成的通知；已经启动过时返回 None
+    fn start_sampler(&mut self) -> Option<Receiver<Instant>> {
+        let mut sampler = self.sampler.take()?;
+        // 读取端复制采集器中的配置（模块的命令行参数，见 Module::configure），所以在 main 设置完之后才创建
+        self.collectors = self.active_collectors();
+        sampler.prime(&mut self.info(), &self.collectors);
+        let (control, samples) = sampler.spawn(Arc::clone(&self.system_info), self.interval);
+        self.control = Some(control);
+        // 第一轮采样就卡住时也要提示数据过时
+        self.last_sample = Some(Instant::now());
+        Some(samples)
+    }
+
+   // 主循环：采样在后台线程中进行，这里只处理输入和重绘
 fn main_loop(&mut self, terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> io::Result<()> {
-    let mut last_update = Instant::now();
-    let tick_rate = Duration::from_millis(250); // 每 250ms 刷新一次UI
+    let Some(samples) = self.start_sampler() else {
+        return Ok(());
+    };
+    // 采集器在第一轮读取之前是空的，最多等一个采样间隔再画第一帧
+    if let Ok(at) = samples.recv_timeout(self.interval) {
+        self.last_sample = Some(at);
+    }
+    // 输入的轮询间隔，决定按键的最大延迟；采样慢的时候界面也不会卡住
+    let poll_rate = Duration::from_millis(50);
+    let mut dirty = true; // 有新数据或按键后才重绘
+    let mut last_draw = Instant::now();
     loop {
-        // 1. 检查用户输入 (非阻塞)
-        if event::poll(tick_rate)? { // <-- 关键点
-            if let Event::Key(key) = event::read()? {
-                match key.code {
-                    KeyCode::Char('q') => break, // 按下 'q' 就退出循环
-                    _ => {}
+        if dirty || (self.stale_for().is_some() && last_draw.elapsed() >= Duration::from_secs(1)) {
+            // 绘制期间持有锁，采样线程读取完一轮后等待绘制结束才写入
+            terminal.draw(|f| draw::draw(f, self, &self.info()))?;
+            dirty = false;
+            last_draw = Instant::now();
+        }
+
+        // 1. 检查用户输入，按键立即处理并重绘
+        if event::poll(poll_rate)? {
+            match event::read()? {
+                // Windows 下按键的按下和松开都会产生事件，只处理按下
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    if !self.handle_key(key) {
+                        break; // 按下 'q' 就退出循环
+                    }
+                    dirty = true;
                 }
+                Event::Resize(_, _) => dirty = true,
+                _ => {}
             }
         }
-        // 更新系统信息
-        // 2. 更新界面 (无论有没有输入都执行)
-        // 检查是否到了该刷新的时间
-        if last_update.elapsed() >= tick_rate {
-            self.system_info.sys.refresh_all(); // <--- 新增：刷新所有系统信息
-            self.system_info.cores.update(); // 更新CPU信息
-            self.system_info.memory.update(); // 更新内存信息
-            self.system_info.net.networks.refresh(true);
-            self.system_info.net.update(); // 更新网络信息
-
 
-            terminal.draw(|f| {
-        // 这里的 f 就是 &mut Frame
-        draw::draw(f, &self);
-    })?;
-            last_update = Instant::now(); // 重置计时器
+        // 2. 取出采样线程的通知，有新数据时重绘
+        // 采样线程 panic 后不再有通知，界面保持最后的数据并提示过时
+        while let Ok(at) = samples.try_recv() {
+            self.last_sample = Some(at);
+            dirty = true;
+        }
+        // needs 也可能取决于读到的数据（例如没有封装温度传感器）
+        if dirty {
+            self.update_collectors();
         }
     }
+    // 返回后 samples 被丢弃，采样线程在下一次发送失败时退出
     Ok(())
 }
 
+/// 处理一次按键，返回 false 表示退出程序
+fn handle_key(&mut self, key: KeyEvent) -> bool {
+    // 正在输入或显示弹出窗口的模块收到所有按键，包括 'q'
+    let modal = self.enabled_modules().find(|module| (module.modal)(self));
+    if let Some(module) = modal {
+        (module.on_key)(self, key.code);
+        return true;
+    }
+    // 上一次操作的结果只显示到下一次按键为止
+    self.status_message = None;
+
+    match key.code {
+        KeyCode::Char('q') => return false,
+        // 采样间隔和历史长度，对所有模块生效
+        KeyCode::Char('[') | KeyCode::Char(']') | KeyCode::Char('{') | KeyCode::Char('}') => {
+            let (interval, history) = match key.code {
+                KeyCode::Char('[') => (step(&INTERVAL_STEPS, self.interval, false), self.history),
+                KeyCode::Char(']') => (step(&INTERVAL_STEPS, self.interval, true), self.history),
+                KeyCode::Char('{') => (self.interval, step(&HISTORY_STEPS, self.history, false)),
+                _ => (self.interval, step(&HISTORY_STEPS, self.history, true)),
+            };
+            self.set_timing(interval, history);
+            self.status_message = Some(format!(
+                "Sampling every {}, keeping {} of history",
+                format_duration(self.interval),
+                format_duration(self.history)
+            ));
+        }
+        // 其余按键交给启用的模块，按注册表的顺序第一个处理它的模块生效
+        code => {
+            let modules: Vec<&'static Module> = self.enabled_modules().collect();
+            for module in modules {
+                if (module.on_key)(self, code) {
+                    break;
+                }
+            }
+            self.update_collectors();
+        }
+    }
+    true
+}
+
 }
 fn setup_terminal() -> io::Result<Terminal<CrosstermBackend<io::Stdout>>> {
     enable_raw_mode()?;
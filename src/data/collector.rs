@@ -0,0 +1,100 @@
+// src/data/collector.rs
+
+use std::time::{Duration, Instant};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+/// 默认的采样间隔
+pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(250);
+/// 默认的历史长度，所有图表显示同样长的时间
+pub const DEFAULT_HISTORY: Duration = Duration::from_secs(60);
+
+/// 采集器提供的一项指标
+pub struct Metric {
+    pub name: &'static str,
+    pub unit: &'static str, // 例如 "%"、"B/s"、"/s"；没有单位时为空字符串
+    pub description: &'static str,
+}
+
+/// 采集前需要 sysinfo 刷新的内容。采样线程合并所有启用的采集器的需求，每轮只刷新一次共享的 System
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Refresh {
+    pub cpu: bool,             // CPU 使用率和频率
+    pub memory: bool,          // 内存和交换分区
+    pub processes: bool,       // 进程列表（状态、父进程、线程类型）
+    pub process_details: bool, // 每个进程的 CPU、内存、用户和命令行，隐含 processes
+}
+
+impl Refresh {
+    /// 合并两个采集器的需求
+    pub fn union(self, other: Refresh) -> Refresh {
+        Refresh {
+            cpu: self.cpu || other.cpu,
+            memory: self.memory || other.memory,
+            processes: self.processes || other.processes,
+            process_details: self.process_details || other.process_details,
+        }
+    }
+
+    /// 只刷新需要的部分；进程表是最大的开销，没有采集器需要时完全不扫描 /proc/<pid>
+    pub fn apply(self, sys: &mut System) {
+        if self.cpu {
+            sys.refresh_cpu_all();
+        }
+        if self.memory {
+            sys.refresh_memory();
+        }
+        if self.processes || self.process_details {
+            let kind = if self.process_details {
+                ProcessRefreshKind::nothing()
+                    .with_cpu()
+                    .with_memory()
+                    .with_user(UpdateKind::OnlyIfNotSet)
+                    .with_cmd(UpdateKind::OnlyIfNotSet)
+            } else {
+                ProcessRefreshKind::nothing()
+            };
+            sys.refresh_processes_specifics(ProcessesToUpdate::All, true, kind);
+        }
+    }
+}
+
+/// 所有数据采集器的统一接口。采集器只保存界面显示的数据，读取 /proc、sysfs 等由它的读取端（Reader）完成
+pub trait Collector {
+    /// 采集器的名称，模块注册表用它声明需要刷新哪些采集器
+    fn name(&self) -> &'static str;
+
+    /// 采集器提供的指标
+    fn metrics(&self) -> &'static [Metric];
+
+    /// 历史记录保留的时长（TimeSeries 的 window），启动时和修改历史长度时调用；没有历史的采集器忽略
+    fn set_history(&mut self, _history: Duration) {}
+
+    /// 清空历史记录。只在部分视图中采样的采集器（见 Module::needs）重新开始采样时调用
+    fn clear_history(&mut self) {}
+}
+
+/// 采集器的读取端，由采样线程持有。所有可能阻塞的读取（挂死的 NFS 上 statvfs 会一直等待）都在这里完成，
+/// 调用时不持有共享数据的锁；计算速率需要的上一次计数也保存在这里
+pub trait Reader: Send + 'static {
+    /// 一次读取的结果，交给 Sampled::apply
+    type Reading: Send + 'static;
+
+    /// 读取前需要 sysinfo 刷新的内容；只读 /proc 或自己持有 sysinfo 对象（Networks、Disks 等）的读取端不需要
+    fn refresh(&self) -> Refresh {
+        Refresh::default()
+    }
+
+    /// 读取一次数据；sys 已经按 refresh() 的需求刷新过，不需要 sysinfo 的读取端可以忽略它
+    fn read(&mut self, sys: &System) -> Self::Reading;
+}
+
+/// 由采样线程更新的采集器：读取在锁外进行，锁内只把结果写入历史
+pub trait Sampled: Collector {
+    type Reader: Reader;
+
+    /// 创建读取端。调用时持有锁，只复制配置（--iface、--disk-fs 等），不做任何 I/O
+    fn reader(&self) -> Self::Reader;
+
+    /// 写入一次读取的结果，at 为读取完成的时间；只做内存中的计算
+    fn apply(&mut self, reading: <Self::Reader as Reader>::Reading, at: Instant);
+}
@@ -0,0 +1,396 @@
+// src/data/connections.rs
+
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use crate::data::collector::{Collector, Metric, Reader, Sampled};
+use sysinfo::System;
+
+/// /proc/net/tcp 中的状态码（下标即状态码），与内核 include/net/tcp_states.h 一致
+const TCP_STATES: [&str; 13] = [
+    "UNKNOWN",
+    "ESTABLISHED",
+    "SYN_SENT",
+    "SYN_RECV",
+    "FIN_WAIT1",
+    "FIN_WAIT2",
+    "TIME_WAIT",
+    "CLOSE",
+    "CLOSE_WAIT",
+    "LAST_ACK",
+    "LISTEN",
+    "CLOSING",
+    "NEW_SYN_RECV",
+];
+
+/// 未连接的 UDP 套接字在内核中的状态是 CLOSE，这里和 ss 一样显示为 UNCONN
+const UDP_UNCONNECTED: &str = "UNCONN";
+
+/// 端口事件日志最多保留的条数
+const MAX_EVENTS: usize = 100;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Protocol {
+    Tcp,
+    Tcp6,
+    Udp,
+    Udp6,
+}
+
+impl Protocol {
+    const ALL: [Protocol; 4] = [Protocol::Tcp, Protocol::Tcp6, Protocol::Udp, Protocol::Udp6];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Protocol::Tcp => "tcp",
+            Protocol::Tcp6 => "tcp6",
+            Protocol::Udp => "udp",
+            Protocol::Udp6 => "udp6",
+        }
+    }
+
+    pub fn is_tcp(self) -> bool {
+        matches!(self, Protocol::Tcp | Protocol::Tcp6)
+    }
+
+    fn path(self) -> String {
+        format!("/proc/net/{}", self.name())
+    }
+}
+
+/// 一个 TCP/UDP 套接字
+pub struct Socket {
+    pub protocol: Protocol,
+    pub local: SocketAddr,
+    pub remote: SocketAddr,
+    pub state: &'static str,
+    pub tx_queue: u64,            // 发送队列中的字节数
+    pub rx_queue: u64,            // 接收队列中的字节数
+    pub inode: u64,
+    pub pid: Option<u32>,         // 没有权限读取 /proc/<pid>/fd 时为 None
+    pub process: Option<String>,  // 所属进程名
+}
+
+impl Socket {
+    /// 是否在监听端口：处于 LISTEN 的 TCP 套接字，或者没有连接远端的 UDP 套接字
+    fn is_listening(&self) -> bool {
+        if self.protocol.is_tcp() {
+            self.state == "LISTEN"
+        } else {
+            self.state == UDP_UNCONNECTED && self.remote.port() == 0
+        }
+    }
+}
+
+/// 一个正在监听的端口
+#[derive(Clone)]
+pub struct ListeningPort {
+    pub protocol: Protocol,
+    pub address: SocketAddr,
+    pub pid: Option<u32>,
+    pub process: Option<String>,
+}
+
+impl ListeningPort {
+    fn same_port(&self, other: &ListeningPort) -> bool {
+        self.protocol == other.protocol && self.address == other.address
+    }
+}
+
+/// 运行期间监听端口的变化
+pub struct PortEvent {
+    pub timestamp: u64, // Unix 时间戳（秒）
+    pub opened: bool,   // true 表示开始监听，false 表示停止监听
+    pub port: ListeningPort,
+}
+
+/// 读取 /proc/net/{tcp,tcp6,udp,udp6}，列出所有套接字并统计各状态的数量
+pub struct Connections {
+    pub sockets: Vec<Socket>,            // 按协议、状态（CLOSE_WAIT 和 ESTABLISHED 在前）、本地端口排序
+    pub states: Vec<(&'static str, usize)>, // 各状态的套接字数量，按 TCP_STATES 的顺序，UNCONN 在最后
+    pub listening: Vec<ListeningPort>,   // 正在监听的端口，按端口号排序
+    pub events: VecDeque<PortEvent>,     // 端口打开/关闭的事件，最新的在最后
+    pub available: bool,                 // 非 Linux 或无法读取 /proc/net 时为 false
+}
+
+impl Connections {
+    pub fn new() -> Connections {
+        Connections {
+            sockets: Vec::new(),
+            states: Vec::new(),
+            listening: Vec::new(),
+            events: VecDeque::with_capacity(MAX_EVENTS),
+            available: false, // 第一次读取后更新
+        }
+    }
+}
+
+/// Connections 的读取端，保存上一次的监听端口用来生成事件
+pub struct ConnectionsReader {
+    listening: Option<Vec<ListeningPort>>, // 第一次读取前为 None
+}
+
+/// 一次读取的结果
+pub struct ConnectionsReading {
+    sockets: Vec<Socket>,
+    states: Vec<(&'static str, usize)>,
+    listening: Vec<ListeningPort>,
+    events: Vec<PortEvent>,
+    available: bool,
+}
+
+impl Reader for ConnectionsReader {
+    type Reading = ConnectionsReading;
+
+    fn read(&mut self, _sys: &System) -> ConnectionsReading {
+        let mut sockets = Vec::new();
+        let mut available = false;
+        for protocol in Protocol::ALL {
+            // 内核可能关闭了 IPv6，只要有一个文件可读就认为可用
+            if let Ok(content) = fs::read_to_string(protocol.path()) {
+                available = true;
+                sockets.extend(content.lines().skip(1).filter_map(|line| parse_socket(protocol, line)));
+            }
+        }
+
+        let owners = socket_owners();
+        for socket in &mut sockets {
+            if let Some((pid, process)) = owners.get(&socket.inode) {
+                socket.pid = Some(*pid);
+                socket.process = Some(process.clone());
+            }
+        }
+        // TCP 在前，UDP 在后；面板放不下所有套接字时，最值得关注的状态排在前面
+        sockets.sort_by_key(|s| (!s.protocol.is_tcp(), socket_order(s.state), s.local.port()));
+
+        let mut counts: HashMap<&'static str, usize> = HashMap::new();
+        for socket in &sockets {
+            *counts.entry(socket.state).or_insert(0) += 1;
+        }
+        let mut states: Vec<(&'static str, usize)> = counts.into_iter().collect();
+        states.sort_by_key(|(state, _)| state_order(state));
+
+        let mut listening: Vec<ListeningPort> = sockets
+            .iter()
+            .filter(|s| s.is_listening())
+            .map(|s| ListeningPort { protocol: s.protocol, address: s.local, pid: s.pid, process: s.process.clone() })
+            .collect();
+        listening.sort_by_key(|p| (p.address.port(), !p.protocol.is_tcp(), p.protocol.name(), p.address));
+        listening.dedup_by(|a, b| a.same_port(b)); // SO_REUSEPORT 时同一个端口会有多个套接字
+
+        // 启动时已经在监听的端口不算事件
+        let events = match &self.listening {
+            Some(previous) => port_events(previous, &listening),
+            None => Vec::new(),
+        };
+        self.listening = Some(listening.clone());
+        ConnectionsReading { sockets, states, listening, events, available }
+    }
+}
+
+/// 和上一次的监听端口比较，列出新打开和已关闭的端口
+fn port_events(previous: &[ListeningPort], listening: &[ListeningPort]) -> Vec<PortEvent> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let closed = previous.iter().filter(|old| !listening.iter().any(|new| new.same_port(old)));
+    let opened = listening.iter().filter(|new| !previous.iter().any(|old| old.same_port(new)));
+    closed
+        .map(|port| PortEvent { timestamp, opened: false, port: port.clone() })
+        .chain(opened.map(|port| PortEvent { timestamp, opened: true, port: port.clone() }))
+        .collect()
+}
+
+/// 状态在汇总中的顺序
+fn state_order(state: &str) -> usize {
+    TCP_STATES.iter().position(|s| *s == state).unwrap_or(TCP_STATES.len())
+}
+
+/// 套接字列表中的顺序：CLOSE_WAIT（通常是程序没有关闭连接）最先，然后是 ESTABLISHED，其余按汇总的顺序
+fn socket_order(state: &str) -> usize {
+    match state {
+        "CLOSE_WAIT" => 0,
+        "ESTABLISHED" => 1,
+        _ => 2 + state_order(state),
+    }
+}
+
+/// 解析 /proc/net/tcp 等文件中的一行：
+/// "sl local_address rem_address st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode ..."
+fn parse_socket(protocol: Protocol, line: &str) -> Option<Socket> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 10 {
+        return None;
+    }
+    let code = usize::from_str_radix(fields[3], 16).ok()?;
+    let state = match TCP_STATES.get(code) {
+        Some(&"CLOSE") if !protocol.is_tcp() => UDP_UNCONNECTED,
+        Some(state) => state,
+        None => TCP_STATES[0],
+    };
+    let (tx_queue, rx_queue) = fields[4].split_once(':')?;
+    Some(Socket {
+        protocol,
+        local: parse_address(fields[1])?,
+        remote: parse_address(fields[2])?,
+        state,
+        tx_queue: u64::from_str_radix(tx_queue, 16).ok()?,
+        rx_queue: u64::from_str_radix(rx_queue, 16).ok()?,
+        inode: fields[9].parse().ok()?,
+        pid: None,
+        process: None,
+    })
+}
+
+/// 解析 "0100007F:1F90" 形式的地址。地址按 32 位字以本机字节序打印，端口是普通的十六进制数
+fn parse_address(text: &str) -> Option<SocketAddr> {
+    let (address, port) = text.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let word = |i: usize| -> Option<[u8; 4]> {
+        let hex = address.get(i * 8..i * 8 + 8)?;
+        Some(u32::from_str_radix(hex, 16).ok()?.to_ne_bytes())
+    };
+    let ip = match address.len() {
+        8 => IpAddr::V4(Ipv4Addr::from(word(0)?)),
+        32 => {
+            let mut bytes = [0u8; 16];
+            for i in 0..4 {
+                bytes[i * 4..i * 4 + 4].copy_from_slice(&word(i)?);
+            }
+            IpAddr::V6(Ipv6Addr::from(bytes))
+        }
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+/// 遍历 /proc/<pid>/fd，建立套接字 inode 到 (PID, 进程名) 的映射；
+/// 没有权限读取的进程会被跳过
+fn socket_owners() -> HashMap<u64, (u32, String)> {
+    let mut owners = HashMap::new();
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return owners;
+    };
+    for entry in entries.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+            continue;
+        };
+        let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        let mut process = None;
+        for fd in fds.flatten() {
+            let Ok(target) = fs::read_link(fd.path()) else {
+                continue;
+            };
+            // 套接字的链接目标形如 "socket:[12345]"
+            let Some(inode) = target
+                .to_str()
+                .and_then(|t| t.strip_prefix("socket:["))
+                .and_then(|t| t.strip_suffix(']'))
+                .and_then(|t| t.parse::<u64>().ok())
+            else {
+                continue;
+            };
+            let process = process.get_or_insert_with(|| {
+                fs::read_to_string(entry.path().join("comm")).map(|s| s.trim().to_string()).unwrap_or_default()
+            });
+            owners.entry(inode).or_insert_with(|| (pid, process.clone()));
+        }
+    }
+    owners
+}
+
+const METRICS: &[Metric] = &[
+    Metric { name: "sockets", unit: "", description: "TCP/UDP 套接字及所属进程" },
+    Metric { name: "states", unit: "", description: "各连接状态的套接字数量" },
+    Metric { name: "listening", unit: "", description: "正在监听的端口" },
+];
+
+impl Collector for Connections {
+    fn name(&self) -> &'static str {
+        "connections"
+    }
+
+    fn metrics(&self) -> &'static [Metric] {
+        METRICS
+    }
+}
+
+impl Sampled for Connections {
+    type Reader = ConnectionsReader;
+
+    fn reader(&self) -> ConnectionsReader {
+        ConnectionsReader { listening: None }
+    }
+
+    fn apply(&mut self, reading: ConnectionsReading, _at: Instant) {
+        self.sockets = reading.sockets;
+        self.states = reading.states;
+        self.listening = reading.listening;
+        self.available = reading.available;
+        for event in reading.events {
+            if self.events.len() == MAX_EVENTS {
+                self.events.pop_front();
+            }
+            self.events.push_back(event);
+        }
+    }
+}
+
+// /proc/net 中的地址按本机字节序打印，下面的行取自小端机器
+#[cfg(all(test, target_endian = "little"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ipv4_address() {
+        assert_eq!(parse_address("0100007F:1F90"), Some("127.0.0.1:8080".parse().unwrap()));
+        assert_eq!(parse_address("00000000:0016"), Some("0.0.0.0:22".parse().unwrap()));
+    }
+
+    #[test]
+    fn parses_ipv6_address() {
+        assert_eq!(parse_address("00000000000000000000000001000000:0277"), Some("[::1]:631".parse().unwrap()));
+        assert_eq!(
+            parse_address("0000000000000000FFFF00000100007F:1F90"),
+            Some("[::ffff:127.0.0.1]:8080".parse().unwrap())
+        );
+        assert_eq!(
+            parse_address("B80D0120000000000000000001000000:01BB"),
+            Some("[2001:db8::1]:443".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_address() {
+        assert_eq!(parse_address("0100007F"), None);
+        assert_eq!(parse_address("0100007:1F90"), None);
+        assert_eq!(parse_address("0100007G:1F90"), None);
+    }
+
+    #[test]
+    fn parses_tcp6_line() {
+        let line = "   0: 00000000000000000000000000000000:0016 00000000000000000000000000000000:0000 0A \
+                    00000000:00000000 00:00000000 00000000     0        0 23456 1 0000000000000000 100 0 0 10 0";
+        let socket = parse_socket(Protocol::Tcp6, line).unwrap();
+        assert_eq!(socket.local, "[::]:22".parse().unwrap());
+        assert_eq!(socket.state, "LISTEN");
+        assert_eq!(socket.inode, 23456);
+        assert!(socket.is_listening());
+    }
+
+    #[test]
+    fn unconnected_udp_is_listening() {
+        let line = "  123: 00000000:0044 00000000:0000 07 00000000:00000000 00:00000000 00000000     0        0 17005 2 0000000000000000 0";
+        let socket = parse_socket(Protocol::Udp, line).unwrap();
+        assert_eq!(socket.state, UDP_UNCONNECTED);
+        assert!(socket.is_listening());
+    }
+
+    #[test]
+    fn close_wait_and_established_sort_first() {
+        let mut states = vec!["LISTEN", "TIME_WAIT", "ESTABLISHED", UDP_UNCONNECTED, "CLOSE_WAIT", "SYN_SENT"];
+        states.sort_by_key(|state| socket_order(state));
+        assert_eq!(states, ["CLOSE_WAIT", "ESTABLISHED", "SYN_SENT", "TIME_WAIT", "LISTEN", UDP_UNCONNECTED]);
+    }
+}
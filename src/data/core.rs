@@ -1,64 +1,176 @@
 use sysinfo::{System};
-use std::collections::VecDeque;
+use std::fs;
+use std::time::{Duration, Instant};
+use crate::data::collector::{Collector, Metric, Reader, Refresh, Sampled, DEFAULT_HISTORY};
+use crate::data::series::TimeSeries;
+
+/// 使用率高于此值 (%) 时才判断是否降频
+const THROTTLE_USAGE: f64 = 80.0;
+/// 频率低于最高频率的这个比例时视为降频。修改这两个值时同时修改 METRICS 中 throttled 的说明
+const THROTTLE_RATIO: f64 = 0.7;
+
 pub struct CoreInfo {
     pub brand : String,
-    pub frequency : u64,
+    pub frequency : u64, // 当前频率 (MHz)，每次采样刷新
     pub vendor_id : String,
-    pub usages : VecDeque<f64>,
-    max_history : usize,
+    pub usages : TimeSeries<f64>,
+    pub frequencies : TimeSeries<f64>, // 频率历史 (MHz)
+    pub max_frequency : Option<u64>, // cpufreq 中的最高频率 (MHz)，没有 cpufreq 时为 None
+    pub governor : Option<String>, // 当前的 scaling governor
+    pub throttled : bool, // 使用率高但频率明显低于最高频率
+}
+
+/// scaling governor 很少变化，每隔这么久才重新读取一次
+const GOVERNOR_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 读取 /sys/devices/system/cpu/cpu<N>/cpufreq 下的一个文件
+fn read_cpufreq(index: usize, name: &str) -> Option<String> {
+    let path = format!("/sys/devices/system/cpu/cpu{}/cpufreq/{}", index, name);
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string())
 }
+
 pub struct Cores {
     pub number : usize,
     pub cores : Vec<CoreInfo>,
-    pub usages : VecDeque<f64>, //这是所有核心的平均使用率
-    pub max_history : usize,
-    system : System,
+    pub usages : TimeSeries<f64>, //这是所有核心的平均使用率
 }
 
 impl Cores {
-    pub fn new()->Cores{
-        let mut system=System::new();
-        system.refresh_cpu_all();
+    /// sys 中需要已经有 CPU 列表
+    pub fn new(system: &System)->Cores{
         let mut cores_info:Vec<CoreInfo>=Vec::new();
         for cpu in system.cpus(){
             let cpu_info=CoreInfo{
                 brand:cpu.brand().to_string(),
                 frequency:cpu.frequency(),
                 vendor_id:cpu.vendor_id().to_string(),
-                usages:VecDeque::new(),
-                max_history:10,
+                usages:TimeSeries::new(DEFAULT_HISTORY),
+                frequencies:TimeSeries::new(DEFAULT_HISTORY),
+                max_frequency:None, // 第一次读取时填入
+                governor:None,
+                throttled:false,
             };
             cores_info.push(cpu_info);
         }
         Cores{
             number:system.cpus().len(),
             cores:cores_info,
-            usages:VecDeque::new(),
-            max_history:10,
-            system:system,
+            usages:TimeSeries::new(DEFAULT_HISTORY),
         }
     }
-    
-    pub fn update(&mut self) {
-        // 1. 刷新所有CPU数据
-        self.system.refresh_cpu_all();
-
-        // 2. 更新每个核心的使用率历史
-        for (i, cpu) in self.system.cpus().iter().enumerate() {
-            let usage = cpu.cpu_usage() as f64;
-            self.cores[i].usages.push_back(usage);
-
-            if self.cores[i].usages.len() > self.cores[i].max_history {
-                self.cores[i].usages.pop_front();
-            }
+
+    /// 当前的 scaling governor；各核心不同时返回第一个核心的
+    pub fn governor(&self) -> Option<&str> {
+        self.cores.iter().find_map(|c| c.governor.as_deref())
+    }
+
+    /// 正在降频的核心数
+    pub fn throttled_count(&self) -> usize {
+        self.cores.iter().filter(|c| c.throttled).count()
+    }
+}
+
+const METRICS: &[Metric] = &[
+    Metric { name: "usage", unit: "%", description: "所有核心的平均使用率" },
+    Metric { name: "core_usage", unit: "%", description: "每个逻辑核心的使用率" },
+    Metric { name: "frequency", unit: "MHz", description: "每个核心的当前频率" },
+    Metric { name: "throttled", unit: "", description: "使用率不低于 80% 而当前频率低于最高频率 70% 的核心数" },
+];
+
+/// Cores 的读取端：使用率和当前频率来自 sysinfo，最高频率和 governor 来自 cpufreq
+pub struct CoresReader {
+    max_read: bool,                  // 已经读取过最高频率
+    governors_read: Option<Instant>, // 上次读取 scaling governor 的时间
+}
+
+/// 一次读取的结果
+pub struct CoresReading {
+    usage: f64,                                // 所有核心的平均使用率
+    cores: Vec<(f64, u64)>,                    // 每个核心的使用率和频率 (MHz)
+    max_frequencies: Option<Vec<Option<u64>>>, // 只有第一次读取时有
+    governors: Option<Vec<Option<String>>>,    // 每隔 GOVERNOR_INTERVAL 读取一次
+}
+
+impl Reader for CoresReader {
+    type Reading = CoresReading;
+
+    fn refresh(&self) -> Refresh {
+        Refresh { cpu: true, ..Refresh::default() }
+    }
+
+    /// sys 需要已经刷新过 CPU 使用率和频率
+    fn read(&mut self, sys: &System) -> CoresReading {
+        let now = Instant::now();
+        let count = sys.cpus().len();
+        // 最高频率不会变化，只读取一次；cpuinfo_max_freq 的单位是 kHz
+        let max_frequencies = (!self.max_read).then(|| {
+            (0..count)
+                .map(|i| read_cpufreq(i, "cpuinfo_max_freq").and_then(|s| s.parse::<u64>().ok()).map(|khz| khz / 1000))
+                .collect()
+        });
+        self.max_read = true;
+        // governor 可能在运行时被修改，但不需要每次采样都读取
+        let governors = self.governors_read.is_none_or(|at| now.duration_since(at) >= GOVERNOR_INTERVAL).then(|| {
+            self.governors_read = Some(now);
+            (0..count).map(|i| read_cpufreq(i, "scaling_governor")).collect()
+        });
+        CoresReading {
+            usage: sys.global_cpu_usage() as f64,
+            cores: sys.cpus().iter().map(|cpu| (cpu.cpu_usage() as f64, cpu.frequency())).collect(),
+            max_frequencies,
+            governors,
+        }
+    }
+}
+
+impl Collector for Cores {
+    fn name(&self) -> &'static str {
+        "cpu"
+    }
+
+    fn metrics(&self) -> &'static [Metric] {
+        METRICS
+    }
+
+    fn set_history(&mut self, history: Duration) {
+        self.usages.set_window(history);
+        for core in &mut self.cores {
+            core.usages.set_window(history);
+            core.frequencies.set_window(history);
         }
+    }
+}
+
+impl Sampled for Cores {
+    type Reader = CoresReader;
 
-        // 3. 计算并更新总体平均使用率历史
-        let total_usage = self.system.global_cpu_usage() as f64;
-        self.usages.push_back(total_usage);
+    fn reader(&self) -> CoresReader {
+        CoresReader { max_read: false, governors_read: None }
+    }
 
-        if self.usages.len() > self.max_history {
-            self.usages.pop_front();
+    fn apply(&mut self, reading: CoresReading, at: Instant) {
+        if let Some(max_frequencies) = reading.max_frequencies {
+            for (core, max) in self.cores.iter_mut().zip(max_frequencies) {
+                core.max_frequency = max;
+            }
+        }
+        if let Some(governors) = reading.governors {
+            for (core, governor) in self.cores.iter_mut().zip(governors) {
+                core.governor = governor;
+            }
+        }
+        // 1. 更新每个核心的使用率和频率历史
+        for (core, (usage, frequency)) in self.cores.iter_mut().zip(reading.cores) {
+            core.usages.push(at, usage);
+            core.frequency = frequency;
+            core.frequencies.push(at, frequency as f64);
+            core.throttled = match core.max_frequency {
+                Some(max) if max > 0 => usage >= THROTTLE_USAGE && (frequency as f64) < max as f64 * THROTTLE_RATIO,
+                _ => false,
+            };
         }
+
+        // 2. 更新总体平均使用率历史
+        self.usages.push(at, reading.usage);
     }
-}
\ No newline at end of file
+}
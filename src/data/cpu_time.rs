@@ -0,0 +1,308 @@
+// src/data/cpu_time.rs
+
+use std::fs;
+use std::time::{Duration, Instant};
+use crate::data::collector::{Collector, Metric, Reader, Sampled, DEFAULT_HISTORY};
+use crate::data::series::{Mean, TimeSeries};
+use sysinfo::System;
+
+/// /proc/stat 中一行 cpu 记录的原始累计节拍数 (jiffies)
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+struct Jiffies {
+    user: u64,
+    nice: u64,
+    system: u64,
+    idle: u64,
+    iowait: u64,
+    irq: u64,
+    softirq: u64,
+    steal: u64,
+}
+
+impl Jiffies {
+    /// 解析 "cpu  8122 0 1272 ..." 这样一行（不含开头的 cpu 名称）。旧内核没有 iowait 之后的列，
+    /// 缺少的列按 0 计算；少于 user / nice / system / idle 四列或有非数字时返回 None
+    fn parse<'a>(fields: impl Iterator<Item = &'a str>) -> Option<Jiffies> {
+        let values: Vec<u64> = fields.map(|v| v.parse().ok()).collect::<Option<_>>()?;
+        if values.len() < 4 {
+            return None;
+        }
+        let get = |i: usize| values.get(i).copied().unwrap_or(0);
+        Some(Jiffies {
+            user: get(0),
+            nice: get(1),
+            system: get(2),
+            idle: get(3),
+            iowait: get(4),
+            irq: get(5),
+            softirq: get(6),
+            steal: get(7),
+        })
+    }
+
+    fn total(&self) -> u64 {
+        self.user + self.nice + self.system + self.idle + self.iowait + self.irq + self.softirq + self.steal
+    }
+}
+
+/// 一个采样周期内各类 CPU 时间所占的百分比
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct CpuTimeSample {
+    pub user: f64,
+    pub nice: f64,
+    pub system: f64,
+    pub idle: f64,
+    pub iowait: f64,
+    pub irq: f64,
+    pub softirq: f64,
+    pub steal: f64,
+}
+
+impl Mean for CpuTimeSample {
+    fn mix(self, other: CpuTimeSample, weight: f64) -> CpuTimeSample {
+        CpuTimeSample {
+            user: self.user.mix(other.user, weight),
+            nice: self.nice.mix(other.nice, weight),
+            system: self.system.mix(other.system, weight),
+            idle: self.idle.mix(other.idle, weight),
+            iowait: self.iowait.mix(other.iowait, weight),
+            irq: self.irq.mix(other.irq, weight),
+            softirq: self.softirq.mix(other.softirq, weight),
+            steal: self.steal.mix(other.steal, weight),
+        }
+    }
+}
+
+impl CpuTimeSample {
+    /// 根据前后两次累计值计算本周期的百分比。总节拍数没有增加（间隔太短，或者计数器回绕、重置）时
+    /// 无法计算，返回 None；单个分量变小时按 0 计算
+    fn between(prev: &Jiffies, cur: &Jiffies) -> Option<CpuTimeSample> {
+        let total = cur.total().checked_sub(prev.total()).filter(|&total| total > 0)?;
+        let pct = |c: u64, p: u64| c.saturating_sub(p) as f64 / total as f64 * 100.0;
+        Some(CpuTimeSample {
+            user: pct(cur.user, prev.user),
+            nice: pct(cur.nice, prev.nice),
+            system: pct(cur.system, prev.system),
+            idle: pct(cur.idle, prev.idle),
+            iowait: pct(cur.iowait, prev.iowait),
+            irq: pct(cur.irq, prev.irq),
+            softirq: pct(cur.softirq, prev.softirq),
+            steal: pct(cur.steal, prev.steal),
+        })
+    }
+}
+
+/// 单个 CPU（或全局汇总）的时间分解历史
+pub struct CpuTimeSeries {
+    pub history: TimeSeries<CpuTimeSample>,
+}
+
+impl CpuTimeSeries {
+    fn new(history: Duration) -> CpuTimeSeries {
+        CpuTimeSeries { history: TimeSeries::new(history) }
+    }
+
+    /// 最近一次采样的结果
+    pub fn latest(&self) -> CpuTimeSample {
+        self.history.latest().copied().unwrap_or_default()
+    }
+}
+
+/// 读取 /proc/stat，按周期计算 user / system / iowait / steal 等时间占比
+pub struct CpuTimes {
+    pub total: CpuTimeSeries,         // 所有核心汇总
+    pub per_core: Vec<CpuTimeSeries>, // 每个逻辑核心
+    pub history: Duration, // 历史记录保留的时长
+    pub available: bool, // 非 Linux 或无法读取 /proc/stat 时为 false
+}
+
+impl CpuTimes {
+    pub fn new() -> CpuTimes {
+        CpuTimes {
+            total: CpuTimeSeries::new(DEFAULT_HISTORY),
+            per_core: Vec::new(),
+            history: DEFAULT_HISTORY,
+            available: false, // 第一次读取后更新
+        }
+    }
+}
+
+/// CpuTimes 的读取端，保存上一次的累计节拍数
+pub struct CpuTimesReader {
+    last: Option<(Jiffies, Vec<Jiffies>)>,
+}
+
+/// 一次读取的结果；第一次读取只建立基线，没有百分比
+pub struct CpuTimesReading {
+    available: bool,
+    total: Option<CpuTimeSample>,
+    per_core: Option<Vec<CpuTimeSample>>, // 核心数量变化（CPU 热插拔）或某个核心无法计算时为 None，重新建立基线
+}
+
+impl Reader for CpuTimesReader {
+    type Reading = CpuTimesReading;
+
+    fn read(&mut self, _sys: &System) -> CpuTimesReading {
+        let content = fs::read_to_string("/proc/stat").unwrap_or_default();
+        let Some((total, cores)) = parse_proc_stat(&content) else {
+            return CpuTimesReading { available: false, total: None, per_core: None };
+        };
+        let (total_sample, per_core) = match &self.last {
+            Some((last_total, last_cores)) => (
+                CpuTimeSample::between(last_total, &total),
+                if last_cores.len() == cores.len() {
+                    last_cores.iter().zip(&cores).map(|(prev, cur)| CpuTimeSample::between(prev, cur)).collect()
+                } else {
+                    None
+                },
+            ),
+            None => (None, None),
+        };
+        self.last = Some((total, cores));
+        CpuTimesReading { available: true, total: total_sample, per_core }
+    }
+}
+
+/// 从 /proc/stat 的内容中取出 (全局汇总, 每个核心) 的累计节拍数；无法解析的核心行被跳过，
+/// 核心数量因此变化时读取端会重新建立基线
+fn parse_proc_stat(content: &str) -> Option<(Jiffies, Vec<Jiffies>)> {
+    let mut total = None;
+    let mut cores = Vec::new();
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            Some("cpu") => total = Jiffies::parse(fields),
+            Some(name) if name.starts_with("cpu") => cores.extend(Jiffies::parse(fields)),
+            _ => {}
+        }
+    }
+    total.map(|t| (t, cores))
+}
+
+const METRICS: &[Metric] = &[
+    Metric { name: "user", unit: "%", description: "用户态时间占比（含 nice）" },
+    Metric { name: "system", unit: "%", description: "内核态时间占比" },
+    Metric { name: "iowait", unit: "%", description: "等待 I/O 的空闲时间占比" },
+    Metric { name: "irq", unit: "%", description: "硬中断和软中断时间占比" },
+    Metric { name: "steal", unit: "%", description: "被虚拟机管理程序占用的时间占比" },
+];
+
+impl Collector for CpuTimes {
+    fn name(&self) -> &'static str {
+        "cpu_times"
+    }
+
+    fn metrics(&self) -> &'static [Metric] {
+        METRICS
+    }
+
+    fn set_history(&mut self, history: Duration) {
+        self.history = history;
+        self.total.history.set_window(history);
+        for series in &mut self.per_core {
+            series.history.set_window(history);
+        }
+    }
+
+    fn clear_history(&mut self) {
+        self.total.history.clear();
+        self.per_core.clear();
+    }
+}
+
+impl Sampled for CpuTimes {
+    type Reader = CpuTimesReader;
+
+    fn reader(&self) -> CpuTimesReader {
+        CpuTimesReader { last: None }
+    }
+
+    fn apply(&mut self, reading: CpuTimesReading, at: Instant) {
+        self.available = reading.available;
+        if let Some(total) = reading.total {
+            self.total.history.push(at, total);
+        }
+        let Some(per_core) = reading.per_core else {
+            return;
+        };
+        // 核心数量变化时丢弃旧的历史
+        if per_core.len() != self.per_core.len() {
+            self.per_core = per_core.iter().map(|_| CpuTimeSeries::new(self.history)).collect();
+        }
+        for (series, sample) in self.per_core.iter_mut().zip(per_core) {
+            series.history.push(at, sample);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STAT: &str = "\
+cpu  1000 10 300 5000 50 20 30 0 0 0
+cpu0 600 10 200 2400 30 20 20 0 0 0
+cpu1 400 0 100 2600 20 0 10 0 0 0
+intr 123456 0 9 0
+ctxt 987654
+btime 1700000000
+";
+
+    #[test]
+    fn parses_total_and_cores() {
+        let (total, cores) = parse_proc_stat(STAT).unwrap();
+        assert_eq!(total.user, 1000);
+        assert_eq!(total.idle, 5000);
+        assert_eq!(total.softirq, 30);
+        assert_eq!(total.total(), 6410);
+        assert_eq!(cores.len(), 2);
+        assert_eq!(cores[1].system, 100);
+    }
+
+    #[test]
+    fn short_lines_fill_missing_columns_with_zero() {
+        // 2.4 内核只有四列
+        let (total, cores) = parse_proc_stat("cpu 10 0 5 85\ncpu0 10 0 5 85\n").unwrap();
+        assert_eq!(total, Jiffies { user: 10, system: 5, idle: 85, ..Jiffies::default() });
+        assert_eq!(cores.len(), 1);
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        assert_eq!(Jiffies::parse("1 2 3".split_whitespace()), None);
+        assert_eq!(Jiffies::parse("1 2 x 4 5".split_whitespace()), None);
+        let (_, cores) = parse_proc_stat("cpu 1 2 3 4\ncpu0 1 2\ncpu1 1 2 3 4\n").unwrap();
+        assert_eq!(cores.len(), 1);
+        // 没有汇总行时整体无法使用
+        assert_eq!(parse_proc_stat("cpu0 1 2 3 4\nctxt 5\n"), None);
+        assert_eq!(parse_proc_stat(""), None);
+    }
+
+    #[test]
+    fn computes_percentages_between_samples() {
+        let prev = Jiffies { user: 100, system: 50, idle: 800, iowait: 50, ..Jiffies::default() };
+        let cur = Jiffies { user: 150, system: 75, idle: 900, iowait: 75, ..Jiffies::default() };
+        let sample = CpuTimeSample::between(&prev, &cur).unwrap();
+        assert_eq!(sample.user, 25.0);
+        assert_eq!(sample.system, 12.5);
+        assert_eq!(sample.idle, 50.0);
+        assert_eq!(sample.iowait, 12.5);
+        assert_eq!(sample.steal, 0.0);
+    }
+
+    #[test]
+    fn counters_going_backwards_give_no_sample() {
+        let older = Jiffies { user: 100, idle: 900, ..Jiffies::default() };
+        let newer = Jiffies { user: 200, idle: 1000, ..Jiffies::default() };
+        // 参数顺序颠倒（或计数器重置）时总数减少
+        assert_eq!(CpuTimeSample::between(&newer, &older), None);
+        // 没有经过任何节拍
+        assert_eq!(CpuTimeSample::between(&newer, &newer), None);
+        // 总数增加但单个分量变小（例如 iowait 在部分内核上会回退）时该分量按 0 计算
+        let prev = Jiffies { user: 100, iowait: 50, idle: 850, ..Jiffies::default() };
+        let cur = Jiffies { user: 150, iowait: 40, idle: 910, ..Jiffies::default() };
+        let sample = CpuTimeSample::between(&prev, &cur).unwrap();
+        assert_eq!(sample.iowait, 0.0);
+        assert!(sample.user > 0.0 && sample.idle > 0.0);
+    }
+}
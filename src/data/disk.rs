@@ -0,0 +1,168 @@
+// src/data/disk.rs
+
+use std::collections::HashSet;
+use std::time::Instant;
+use sysinfo::{Disks, System};
+use crate::data::collector::{Collector, Metric, Reader, Sampled};
+
+/// 默认忽略的伪文件系统，可以通过 --disk-fs 重新包含
+pub const DEFAULT_IGNORED_FS: [&str; 5] = ["tmpfs", "devtmpfs", "overlay", "squashfs", "ramfs"];
+
+/// 一个挂载点的空间使用情况
+pub struct DiskEntry {
+    pub mount_point: String,
+    pub device: String,
+    pub fs_type: String,
+    pub total: u64,     // 字节
+    pub used: u64,      // 字节
+    pub available: u64, // 字节，普通用户可用的空间
+    pub inodes: Option<(u64, u64)>, // (已用, 总数)，不支持的平台或文件系统为 None
+}
+
+impl DiskEntry {
+    /// 空间使用率 (%)。和 df 一样按 已用 / (已用 + 可用) 计算，保留块不计入，普通用户看到的就是这个比例
+    pub fn usage_percent(&self) -> f64 {
+        let usable = self.used + self.available;
+        if usable == 0 {
+            0.0
+        } else {
+            self.used as f64 / usable as f64 * 100.0
+        }
+    }
+
+    /// inode 使用率 (%)
+    pub fn inode_percent(&self) -> Option<f64> {
+        match self.inodes {
+            Some((used, total)) if total > 0 => Some(used as f64 / total as f64 * 100.0),
+            _ => None,
+        }
+    }
+}
+
+/// 磁盘空间信息
+pub struct DiskInfo {
+    pub entries: Vec<DiskEntry>,
+    pub include_fs: Vec<String>, // 在默认忽略列表中但仍要显示的文件系统类型，"all" 表示不过滤
+}
+
+impl DiskInfo {
+    pub fn new() -> DiskInfo {
+        DiskInfo {
+            entries: Vec::new(), // 第一次读取后填入
+            include_fs: Vec::new(),
+        }
+    }
+}
+
+/// DiskInfo 的读取端，持有 sysinfo 的 Disks。挂死的网络文件系统上 statvfs 会一直阻塞
+pub struct DiskReader {
+    disks: Disks,
+    include_fs: Vec<String>,
+}
+
+impl DiskReader {
+    /// 文件系统类型是否应该显示
+    fn is_included(&self, fs_type: &str) -> bool {
+        if self.include_fs.iter().any(|fs| fs == "all" || fs == fs_type) {
+            return true;
+        }
+        !DEFAULT_IGNORED_FS.contains(&fs_type)
+    }
+}
+
+impl Reader for DiskReader {
+    /// 所有显示的文件系统，按挂载点排序
+    type Reading = Vec<DiskEntry>;
+
+    fn read(&mut self, _sys: &System) -> Vec<DiskEntry> {
+        // 重新读取挂载列表，这样新挂载或卸载的文件系统也能反映出来
+        self.disks.refresh(true);
+        let mut entries: Vec<DiskEntry> = self
+            .disks
+            .list()
+            .iter()
+            .filter(|disk| self.is_included(&disk.file_system().to_string_lossy()))
+            .map(|disk| {
+                // 和 df 一样从 statvfs 计算；读取失败时退回 sysinfo 的数据，此时保留块会算作已用
+                let stat = statvfs(disk.mount_point());
+                let total = disk.total_space();
+                let available = disk.available_space();
+                let space = stat.map_or((total, total.saturating_sub(available), available), |s| s.space);
+                DiskEntry {
+                    mount_point: disk.mount_point().to_string_lossy().to_string(),
+                    device: disk.name().to_string_lossy().to_string(),
+                    fs_type: disk.file_system().to_string_lossy().to_string(),
+                    total: space.0,
+                    used: space.1,
+                    available: space.2,
+                    inodes: stat.and_then(|s| s.inodes),
+                }
+            })
+            .collect();
+
+        // 同一块设备可能被挂载多次（例如 bind mount），只保留路径最短的挂载点。
+        // btrfs 的子卷（/、/home 等）通常分别挂载自同一个设备，它们是用户关心的不同目录，全部保留；
+        // 代价是 btrfs 上的 bind mount 也会重复显示
+        entries.sort_by(|a, b| (a.mount_point.len(), &a.mount_point).cmp(&(b.mount_point.len(), &b.mount_point)));
+        let mut seen = HashSet::new();
+        entries.retain(|e| e.fs_type == "btrfs" || !e.device.starts_with("/dev/") || seen.insert(e.device.clone()));
+        entries.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+        entries
+    }
+}
+
+/// statvfs 中的空间和 inode 使用量
+#[derive(Clone, Copy)]
+struct FsStat {
+    space: (u64, u64, u64),     // (总量, 已用, 普通用户可用)，字节
+    inodes: Option<(u64, u64)>, // (已用, 总数)
+}
+
+/// 通过 statvfs 读取空间和 inode 使用量。已用空间是 f_blocks - f_bfree，不包括只有 root 能用的保留块，
+/// 所以 USED + AVAIL 可能小于 SIZE，与 df 一致
+#[cfg(unix)]
+fn statvfs(mount_point: &std::path::Path) -> Option<FsStat> {
+    let stat = rustix::fs::statvfs(mount_point).ok()?;
+    let block = stat.f_frsize;
+    let space = (
+        stat.f_blocks * block,
+        stat.f_blocks.saturating_sub(stat.f_bfree) * block,
+        stat.f_bavail * block,
+    );
+    // 部分文件系统（如 btrfs）不使用固定数量的 inode
+    let inodes = (stat.f_files > 0).then(|| (stat.f_files.saturating_sub(stat.f_ffree), stat.f_files));
+    Some(FsStat { space, inodes })
+}
+
+#[cfg(not(unix))]
+fn statvfs(_mount_point: &std::path::Path) -> Option<FsStat> {
+    None
+}
+
+const METRICS: &[Metric] = &[
+    Metric { name: "used", unit: "B", description: "每个文件系统的已用空间" },
+    Metric { name: "available", unit: "B", description: "普通用户可用的空间" },
+    Metric { name: "inodes", unit: "", description: "已用和总 inode 数" },
+];
+
+impl Collector for DiskInfo {
+    fn name(&self) -> &'static str {
+        "disk"
+    }
+
+    fn metrics(&self) -> &'static [Metric] {
+        METRICS
+    }
+}
+
+impl Sampled for DiskInfo {
+    type Reader = DiskReader;
+
+    fn reader(&self) -> DiskReader {
+        DiskReader { disks: Disks::new(), include_fs: self.include_fs.clone() }
+    }
+
+    fn apply(&mut self, entries: Vec<DiskEntry>, _at: Instant) {
+        self.entries = entries;
+    }
+}
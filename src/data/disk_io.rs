@@ -0,0 +1,349 @@
+// src/data/disk_io.rs
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use crate::data::collector::{Collector, Metric, Reader, Sampled, DEFAULT_HISTORY};
+use crate::data::series::TimeSeries;
+use sysinfo::System;
+
+/// /proc/diskstats 中扇区的大小固定为 512 字节
+const SECTOR_SIZE: u64 = 512;
+
+/// /proc/diskstats 一行中的累计计数
+#[derive(Clone, Copy, Default)]
+struct DiskStats {
+    reads: u64,           // 完成的读请求数
+    sectors_read: u64,
+    read_ms: u64,         // 读请求花费的总时间
+    writes: u64,          // 完成的写请求数
+    sectors_written: u64,
+    write_ms: u64,        // 写请求花费的总时间
+    io_ms: u64,           // 设备忙碌的总时间
+}
+
+/// 单个块设备的 I/O 速率
+pub struct DiskIoDevice {
+    pub name: String,
+    pub read_rate: f64,   // 读取速率 (KB/s)
+    pub write_rate: f64,  // 写入速率 (KB/s)
+    pub read_iops: f64,
+    pub write_iops: f64,
+    pub await_ms: f64,    // 每个请求的平均耗时 (ms)
+    pub utilization: f64, // 设备忙碌时间占比 (%)
+    pub read_rates: TimeSeries<f64>,  // 读取速率历史
+    pub write_rates: TimeSeries<f64>, // 写入速率历史
+}
+
+impl DiskIoDevice {
+    fn new(name: String, history: Duration) -> DiskIoDevice {
+        DiskIoDevice {
+            name,
+            read_rate: 0.0,
+            write_rate: 0.0,
+            read_iops: 0.0,
+            write_iops: 0.0,
+            await_ms: 0.0,
+            utilization: 0.0,
+            read_rates: TimeSeries::new(history),
+            write_rates: TimeSeries::new(history),
+        }
+    }
+
+    fn update(&mut self, rates: DeviceRates, at: Instant) {
+        self.read_rate = rates.read_rate;
+        self.write_rate = rates.write_rate;
+        self.read_iops = rates.read_iops;
+        self.write_iops = rates.write_iops;
+        self.await_ms = rates.await_ms;
+        self.utilization = rates.utilization;
+
+        // 更新历史数据
+        self.read_rates.push(at, self.read_rate);
+        self.write_rates.push(at, self.write_rate);
+    }
+}
+
+/// 一个采样周期内的速率，字段含义同 DiskIoDevice
+pub struct DeviceRates {
+    read_rate: f64,
+    write_rate: f64,
+    read_iops: f64,
+    write_iops: f64,
+    await_ms: f64,
+    utilization: f64,
+}
+
+impl DeviceRates {
+    /// 根据前后两次累计计数计算速率；计数变小（32 位内核上回绕，或设备被重新挂载）时该项按 0 计算
+    fn between(last: &DiskStats, stats: &DiskStats, seconds: f64) -> DeviceRates {
+        let reads = stats.reads.saturating_sub(last.reads);
+        let writes = stats.writes.saturating_sub(last.writes);
+        let busy_ms = stats.read_ms.saturating_sub(last.read_ms) + stats.write_ms.saturating_sub(last.write_ms);
+        DeviceRates {
+            read_rate: (stats.sectors_read.saturating_sub(last.sectors_read) * SECTOR_SIZE) as f64 / seconds / 1024.0,
+            write_rate: (stats.sectors_written.saturating_sub(last.sectors_written) * SECTOR_SIZE) as f64
+                / seconds
+                / 1024.0,
+            read_iops: reads as f64 / seconds,
+            write_iops: writes as f64 / seconds,
+            await_ms: if reads + writes > 0 { busy_ms as f64 / (reads + writes) as f64 } else { 0.0 },
+            utilization: (stats.io_ms.saturating_sub(last.io_ms) as f64 / (seconds * 1000.0) * 100.0).min(100.0),
+        }
+    }
+}
+
+/// 读取 /proc/diskstats，计算每个块设备的吞吐量、IOPS、平均等待时间和利用率
+pub struct DiskIo {
+    pub devices: Vec<DiskIoDevice>,
+    pub selected: Option<String>, // 图表中显示的设备名称，None 时显示第一个设备
+    pub history: Duration, // 历史记录保留的时长
+    pub available: bool, // 非 Linux 或无法读取 /proc/diskstats 时为 false
+}
+
+impl DiskIo {
+    pub fn new() -> DiskIo {
+        DiskIo {
+            devices: Vec::new(), // 第一次读取后填入
+            selected: None,
+            history: DEFAULT_HISTORY,
+            available: false,
+        }
+    }
+
+    /// 图表中当前显示的设备
+    pub fn selected_device(&self) -> Option<&DiskIoDevice> {
+        self.selected
+            .as_ref()
+            .and_then(|name| self.devices.iter().find(|d| d.name == *name))
+            .or_else(|| self.devices.first())
+    }
+
+    /// 切换到下一个设备
+    pub fn select_next(&mut self) {
+        if self.devices.is_empty() {
+            return;
+        }
+        let index = self
+            .selected
+            .as_ref()
+            .and_then(|name| self.devices.iter().position(|d| d.name == *name))
+            .map_or(1, |i| i + 1)
+            % self.devices.len();
+        self.selected = Some(self.devices[index].name.clone());
+    }
+}
+
+/// DiskIo 的读取端，保存每个设备上一次的累计计数
+pub struct DiskIoReader {
+    last: HashMap<String, DiskStats>,
+    last_updated: Instant,
+}
+
+/// 一次读取中的一个设备；新出现的设备只有基线，从下次采样开始计算速率
+pub struct DeviceReading {
+    name: String,
+    rates: Option<DeviceRates>,
+}
+
+impl Reader for DiskIoReader {
+    /// 所有整块磁盘，按 /proc/diskstats 中的顺序；无法读取时为 None
+    type Reading = Option<Vec<DeviceReading>>;
+
+    fn read(&mut self, _sys: &System) -> Option<Vec<DeviceReading>> {
+        let content = fs::read_to_string("/proc/diskstats").ok()?;
+        let stats = parse_diskstats(&content, is_whole_disk);
+        let now = Instant::now();
+        let seconds = now.duration_since(self.last_updated).as_secs_f64();
+        let devices = stats
+            .iter()
+            .map(|(name, s)| DeviceReading {
+                name: name.clone(),
+                rates: self.last.get(name).filter(|_| seconds > 0.0).map(|last| DeviceRates::between(last, s, seconds)),
+            })
+            .collect();
+        if seconds > 0.0 {
+            self.last = stats.into_iter().collect();
+            self.last_updated = now;
+        }
+        Some(devices)
+    }
+}
+
+/// 是否是整块磁盘（而不是分区、loop 或 ram 设备）
+fn is_whole_disk(name: &str) -> bool {
+    if name.starts_with("loop") || name.starts_with("ram") {
+        return false;
+    }
+    let sys_block = Path::new("/sys/block");
+    // 没有 /sys 时无法区分分区，全部保留
+    !sys_block.exists() || sys_block.join(name).exists()
+}
+
+/// 从 /proc/diskstats 的内容中取出 (设备名, 累计计数) 列表，按文件中的顺序；
+/// 只保留 include 接受的设备，列数不足或计数不是数字的行被跳过
+fn parse_diskstats(content: &str, include: impl Fn(&str) -> bool) -> Vec<(String, DiskStats)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 14 {
+                return None;
+            }
+            let name = fields[2];
+            if !include(name) {
+                return None;
+            }
+            let get = |i: usize| fields[i].parse::<u64>().ok();
+            let stats = DiskStats {
+                reads: get(3)?,
+                sectors_read: get(5)?,
+                read_ms: get(6)?,
+                writes: get(7)?,
+                sectors_written: get(9)?,
+                write_ms: get(10)?,
+                io_ms: get(12)?,
+            };
+            Some((name.to_string(), stats))
+        })
+        .collect()
+}
+
+const METRICS: &[Metric] = &[
+    Metric { name: "read", unit: "KB/s", description: "每个块设备的读取速率" },
+    Metric { name: "write", unit: "KB/s", description: "每个块设备的写入速率" },
+    Metric { name: "iops", unit: "/s", description: "每秒读写请求数" },
+    Metric { name: "await", unit: "ms", description: "每个请求的平均耗时" },
+    Metric { name: "utilization", unit: "%", description: "设备忙碌时间占比" },
+];
+
+impl Collector for DiskIo {
+    fn name(&self) -> &'static str {
+        "disk_io"
+    }
+
+    fn metrics(&self) -> &'static [Metric] {
+        METRICS
+    }
+
+    fn set_history(&mut self, history: Duration) {
+        self.history = history;
+        for device in &mut self.devices {
+            device.read_rates.set_window(history);
+            device.write_rates.set_window(history);
+        }
+    }
+}
+
+impl Sampled for DiskIo {
+    type Reader = DiskIoReader;
+
+    fn reader(&self) -> DiskIoReader {
+        DiskIoReader { last: HashMap::new(), last_updated: Instant::now() }
+    }
+
+    fn apply(&mut self, reading: Option<Vec<DeviceReading>>, at: Instant) {
+        self.available = reading.is_some();
+        let Some(reading) = reading else {
+            return;
+        };
+        // 已移除的设备不再显示
+        let mut devices = Vec::with_capacity(reading.len());
+        for new in reading {
+            let mut device = match self.devices.iter().position(|d| d.name == new.name) {
+                Some(pos) => self.devices.swap_remove(pos),
+                None => DiskIoDevice::new(new.name, self.history),
+            };
+            if let Some(rates) = new.rates {
+                device.update(rates, at);
+            }
+            devices.push(device);
+        }
+        self.devices = devices;
+        // 选中的设备被移除后回到第一个设备，设备列表顺序变化时选择不变
+        if self.selected.as_ref().is_some_and(|name| !self.devices.iter().any(|d| d.name == *name)) {
+            self.selected = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 4.18 之后的内核每行有 20 列（增加了 discard 和 flush），更早的内核为 14 列
+    const DISKSTATS: &str = "\
+ 253       0 vda 12000 300 960000 4000 8000 500 640000 6000 0 9000 10000 0 0 0 0 200 100
+ 253       1 vda1 11000 300 950000 3900 7900 500 630000 5900 0 8900 9800 0 0 0 0 0 0
+   7       0 loop0 50 0 400 10 0 0 0 0 0 20 10
+   8       0 sda 100 0 2048 50 20 0 160 30 0 70 80
+";
+
+    #[test]
+    fn parses_whole_disks() {
+        let devices = parse_diskstats(DISKSTATS, |name| !name.starts_with("loop") && name != "vda1");
+        let names: Vec<&str> = devices.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["vda", "sda"]);
+        let vda = devices[0].1;
+        assert_eq!(vda.reads, 12000);
+        assert_eq!(vda.sectors_read, 960000);
+        assert_eq!(vda.writes, 8000);
+        assert_eq!(vda.sectors_written, 640000);
+        assert_eq!(vda.io_ms, 9000);
+    }
+
+    #[test]
+    fn skips_short_and_malformed_lines() {
+        let content = "   8 0 sda 1 2 3\n   8 16 sdb 1 0 x 4 5 0 6 7 0 8 9\n   8 32 sdc 1 0 2 3 4 0 5 6 0 7 8\n";
+        let devices = parse_diskstats(content, |_| true);
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].0, "sdc");
+        assert!(parse_diskstats("", |_| true).is_empty());
+    }
+
+    #[test]
+    fn computes_rates_between_samples() {
+        let last = DiskStats { reads: 100, sectors_read: 1000, read_ms: 50, writes: 10, io_ms: 100, ..DiskStats::default() };
+        let stats = DiskStats { reads: 200, sectors_read: 3048, read_ms: 150, writes: 10, io_ms: 600, ..DiskStats::default() };
+        let rates = DeviceRates::between(&last, &stats, 2.0);
+        assert_eq!(rates.read_rate, 512.0); // 2048 个扇区 = 1 MB，两秒
+        assert_eq!(rates.read_iops, 50.0);
+        assert_eq!(rates.write_iops, 0.0);
+        assert_eq!(rates.await_ms, 1.0);
+        assert_eq!(rates.utilization, 25.0);
+    }
+
+    #[test]
+    fn counters_going_backwards_give_zero_rates() {
+        let older = DiskStats { reads: 100, sectors_read: 1000, read_ms: 50, writes: 10, io_ms: 100, ..DiskStats::default() };
+        let newer = DiskStats { reads: 200, sectors_read: 3048, read_ms: 150, writes: 20, io_ms: 600, ..DiskStats::default() };
+        let rates = DeviceRates::between(&newer, &older, 1.0);
+        assert_eq!(rates.read_rate, 0.0);
+        assert_eq!(rates.write_rate, 0.0);
+        assert_eq!(rates.read_iops, 0.0);
+        assert_eq!(rates.await_ms, 0.0);
+        assert_eq!(rates.utilization, 0.0);
+    }
+
+    #[test]
+    fn selection_follows_the_device_name() {
+        let reading = |names: &[&str]| {
+            Some(names.iter().map(|name| DeviceReading { name: name.to_string(), rates: None }).collect())
+        };
+        let mut disk_io = DiskIo::new();
+        disk_io.apply(reading(&["sda", "sdb"]), Instant::now());
+        assert_eq!(disk_io.selected_device().unwrap().name, "sda");
+        disk_io.select_next();
+        assert_eq!(disk_io.selected_device().unwrap().name, "sdb");
+        // 插入新设备后仍然显示 sdb
+        disk_io.apply(reading(&["nvme0n1", "sda", "sdb"]), Instant::now());
+        assert_eq!(disk_io.selected_device().unwrap().name, "sdb");
+        disk_io.select_next();
+        assert_eq!(disk_io.selected_device().unwrap().name, "nvme0n1");
+        // 选中的设备被移除后回到第一个设备
+        disk_io.apply(reading(&["sda"]), Instant::now());
+        assert_eq!(disk_io.selected, None);
+        assert_eq!(disk_io.selected_device().unwrap().name, "sda");
+    }
+}
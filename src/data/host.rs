@@ -0,0 +1,40 @@
+// src/data/host.rs
+
+use sysinfo::System;
+
+/// 主机的静态信息，启动时读取一次
+pub struct HostInfo {
+    pub hostname: String,
+    pub os: String,      // 发行版名称和版本，例如 "Ubuntu 22.04"
+    pub kernel: String,  // 内核版本
+}
+
+impl HostInfo {
+    pub fn new() -> HostInfo {
+        let unknown = || "unknown".to_string();
+        HostInfo {
+            hostname: System::host_name().unwrap_or_else(unknown),
+            os: System::long_os_version()
+                .or_else(System::name)
+                .unwrap_or_else(unknown),
+            kernel: System::kernel_version().unwrap_or_else(unknown),
+        }
+    }
+
+    /// 已运行的时间（秒）
+    pub fn uptime(&self) -> u64 {
+        System::uptime()
+    }
+
+    /// 把运行时间格式化为 "3d 04:12" 或 "04:12"
+    pub fn format_uptime(seconds: u64) -> String {
+        let days = seconds / 86_400;
+        let hours = seconds % 86_400 / 3600;
+        let minutes = seconds % 3600 / 60;
+        if days > 0 {
+            format!("{}d {:02}:{:02}", days, hours, minutes)
+        } else {
+            format!("{:02}:{:02}", hours, minutes)
+        }
+    }
+}
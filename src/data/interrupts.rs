@@ -0,0 +1,309 @@
+// src/data/interrupts.rs
+
+use std::collections::HashMap;
+use std::fs;
+use std::time::{Duration, Instant};
+use crate::data::collector::{Collector, Metric, Reader, Sampled, DEFAULT_HISTORY};
+use crate::data::series::{Mean, TimeSeries};
+use sysinfo::System;
+
+/// 一个采样周期内每秒的上下文切换、硬中断和软中断次数（来自 /proc/stat）
+#[derive(Clone, Copy, Default)]
+pub struct InterruptSample {
+    pub context_switches: f64,
+    pub interrupts: f64,
+    pub softirqs: f64,
+}
+
+impl Mean for InterruptSample {
+    fn mix(self, other: InterruptSample, weight: f64) -> InterruptSample {
+        InterruptSample {
+            context_switches: self.context_switches.mix(other.context_switches, weight),
+            interrupts: self.interrupts.mix(other.interrupts, weight),
+            softirqs: self.softirqs.mix(other.softirqs, weight),
+        }
+    }
+}
+
+/// /proc/interrupts 或 /proc/softirqs 中的一行
+pub struct Irq {
+    pub name: String,      // IRQ 号（"24"）、架构相关中断（"LOC"）或软中断类型（"NET_RX"）
+    pub device: String,    // 设备名或说明，例如 "eth0-TxRx-0"、"Local timer interrupts"
+    pub softirq: bool,     // 来自 /proc/softirqs
+    pub total: u64,        // 开机以来所有 CPU 上的累计次数
+    pub per_cpu: Vec<f64>, // 每个 CPU 上每秒的次数
+    pub rate: f64,         // 所有 CPU 合计每秒的次数
+}
+
+/// 读取 /proc/stat、/proc/interrupts 和 /proc/softirqs，计算中断和上下文切换速率
+pub struct Interrupts {
+    pub samples: TimeSeries<InterruptSample>,
+    pub irqs: Vec<Irq>,  // 按每秒次数从高到低排序
+    pub cpus: usize,     // /proc/interrupts 表头中的 CPU 数量
+    pub available: bool, // 非 Linux 或无法读取 /proc/stat 时为 false
+}
+
+impl Interrupts {
+    pub fn new() -> Interrupts {
+        Interrupts {
+            samples: TimeSeries::new(DEFAULT_HISTORY),
+            irqs: Vec::new(),
+            cpus: 0,
+            available: false, // 第一次读取后更新
+        }
+    }
+
+    /// 最近一次采样的结果
+    pub fn latest(&self) -> InterruptSample {
+        self.samples.latest().copied().unwrap_or_default()
+    }
+
+    /// 每个 CPU 上所有硬中断和软中断合计的每秒次数
+    pub fn per_cpu_totals(&self) -> Vec<f64> {
+        let mut totals = vec![0.0; self.cpus];
+        for irq in &self.irqs {
+            for (total, rate) in totals.iter_mut().zip(&irq.per_cpu) {
+                *total += rate;
+            }
+        }
+        totals
+    }
+}
+
+/// Interrupts 的读取端，保存上一次的累计值
+pub struct InterruptsReader {
+    last_stat: Option<[u64; 3]>,            // ctxt、intr、softirq 的累计值
+    last_counts: HashMap<String, Vec<u64>>, // 每行每个 CPU 的累计值
+    last_updated: Instant,
+}
+
+/// 一次读取的结果；无法读取 /proc/stat 时整个读取为 None
+pub struct InterruptsReading {
+    sample: Option<InterruptSample>, // 第一次读取只建立基线
+    irqs: Vec<Irq>,
+    cpus: Option<usize>, // 无法读取 /proc/interrupts 时为 None
+}
+
+impl Reader for InterruptsReader {
+    type Reading = Option<InterruptsReading>;
+
+    fn read(&mut self, _sys: &System) -> Option<InterruptsReading> {
+        let now = Instant::now();
+        let seconds = now.duration_since(self.last_updated).as_secs_f64();
+
+        let stat = parse_stat(&fs::read_to_string("/proc/stat").ok()?)?;
+        let sample = self.last_stat.map(|last| InterruptSample {
+            context_switches: rate(stat[0], last[0], seconds),
+            interrupts: rate(stat[1], last[1], seconds),
+            softirqs: rate(stat[2], last[2], seconds),
+        });
+        self.last_stat = Some(stat);
+
+        let mut rows = Vec::new();
+        let mut cpus = None;
+        let read_table = |path: &str, softirq: bool| parse_table(&fs::read_to_string(path).ok()?, softirq);
+        if let Some((count, interrupts)) = read_table("/proc/interrupts", false) {
+            cpus = Some(count);
+            rows.extend(interrupts);
+        }
+        if let Some((_, softirqs)) = read_table("/proc/softirqs", true) {
+            rows.extend(softirqs);
+        }
+
+        let mut counts = HashMap::with_capacity(rows.len());
+        let mut irqs: Vec<Irq> = rows
+            .into_iter()
+            .map(|(name, device, softirq, values)| {
+                let key = if softirq { format!("softirq:{}", name) } else { name.clone() };
+                // 新出现的行没有上一次的值，速率记为 0
+                let previous = self.last_counts.get(&key);
+                let per_cpu: Vec<f64> = values
+                    .iter()
+                    .enumerate()
+                    .map(|(cpu, &value)| {
+                        let prev = previous.and_then(|p| p.get(cpu)).copied().unwrap_or(value);
+                        rate(value, prev, seconds)
+                    })
+                    .collect();
+                let irq = Irq {
+                    name,
+                    device,
+                    softirq,
+                    total: values.iter().sum(),
+                    rate: per_cpu.iter().sum(),
+                    per_cpu,
+                };
+                counts.insert(key, values);
+                irq
+            })
+            .collect();
+        irqs.sort_by(|a, b| b.rate.total_cmp(&a.rate).then(b.total.cmp(&a.total)));
+        self.last_counts = counts;
+        self.last_updated = now;
+        Some(InterruptsReading { sample, irqs, cpus })
+    }
+}
+
+/// 每秒的次数；计数变小（旧内核上 32 位的计数回绕）或间隔为 0 时按 0 计算
+fn rate(cur: u64, prev: u64, seconds: f64) -> f64 {
+    if seconds > 0.0 {
+        cur.saturating_sub(prev) as f64 / seconds
+    } else {
+        0.0
+    }
+}
+
+/// 从 /proc/stat 的内容中取出 ctxt、intr 和 softirq 的累计值（intr 和 softirq 只取第一列的总数）
+fn parse_stat(content: &str) -> Option<[u64; 3]> {
+    let mut values = [None; 3];
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        let index = match fields.next() {
+            Some("ctxt") => 0,
+            Some("intr") => 1,
+            Some("softirq") => 2,
+            _ => continue,
+        };
+        values[index] = fields.next().and_then(|v| v.parse().ok());
+    }
+    Some([values[0]?, values[1]?, values[2]?])
+}
+
+/// (名称, 说明, 是否软中断, 每个 CPU 的累计值)
+type TableRow = (String, String, bool, Vec<u64>);
+
+/// 解析 /proc/interrupts 或 /proc/softirqs 的内容：第一行是 "CPU0 CPU1 ..." 表头，
+/// 之后每行是 "名称: 每个 CPU 的计数 说明"；返回 CPU 数量和每一行，没有冒号的行被跳过
+fn parse_table(content: &str, softirq: bool) -> Option<(usize, Vec<TableRow>)> {
+    let mut lines = content.lines();
+    let cpus = lines.next()?.split_whitespace().count();
+    let rows = lines
+        .filter_map(|line| {
+            let (name, rest) = line.split_once(':')?;
+            let name = name.trim().to_string();
+            let mut fields = rest.split_whitespace().peekable();
+            let mut values = Vec::with_capacity(cpus);
+            // ERR、MIS 等行只有一个总数，不足 CPU 数量的部分当作 0
+            while values.len() < cpus
+                && let Some(value) = fields.peek().and_then(|v| v.parse::<u64>().ok())
+            {
+                values.push(value);
+                fields.next();
+            }
+            values.resize(cpus, 0);
+            let description: Vec<&str> = fields.collect();
+            // 数字 IRQ 的说明以中断控制器和触发方式开头，例如 "IR-PCI-MSI 524288-edge eth0"，只保留设备名
+            let device = if name.chars().all(|c| c.is_ascii_digit()) && description.len() > 2 {
+                description[2..].join(" ")
+            } else {
+                description.join(" ")
+            };
+            Some((name, device, softirq, values))
+        })
+        .collect();
+    Some((cpus, rows))
+}
+
+const METRICS: &[Metric] = &[
+    Metric { name: "context_switches", unit: "/s", description: "上下文切换次数" },
+    Metric { name: "interrupts", unit: "/s", description: "硬中断次数" },
+    Metric { name: "softirqs", unit: "/s", description: "软中断次数" },
+    Metric { name: "irq", unit: "/s", description: "每个 IRQ 和软中断类型在各 CPU 上的次数" },
+];
+
+impl Collector for Interrupts {
+    fn name(&self) -> &'static str {
+        "interrupts"
+    }
+
+    fn metrics(&self) -> &'static [Metric] {
+        METRICS
+    }
+
+    fn set_history(&mut self, history: Duration) {
+        self.samples.set_window(history);
+    }
+}
+
+impl Sampled for Interrupts {
+    type Reader = InterruptsReader;
+
+    fn reader(&self) -> InterruptsReader {
+        InterruptsReader { last_stat: None, last_counts: HashMap::new(), last_updated: Instant::now() }
+    }
+
+    fn apply(&mut self, reading: Option<InterruptsReading>, at: Instant) {
+        self.available = reading.is_some();
+        let Some(reading) = reading else {
+            return;
+        };
+        if let Some(sample) = reading.sample {
+            self.samples.push(at, sample);
+        }
+        if let Some(cpus) = reading.cpus {
+            self.cpus = cpus;
+        }
+        self.irqs = reading.irqs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERRUPTS: &str = "\
+           CPU0       CPU1
+  0:         22          0   IO-APIC   2-edge      timer
+ 24:     123456       7890   PCI-MSI 524288-edge      eth0-TxRx-0
+NMI:          3          4   Non-maskable interrupts
+LOC:    9876543    8765432   Local timer interrupts
+ERR:          0
+garbage line without colon
+";
+
+    #[test]
+    fn parses_interrupts_table() {
+        let (cpus, rows) = parse_table(INTERRUPTS, false).unwrap();
+        assert_eq!(cpus, 2);
+        assert_eq!(rows.len(), 5);
+        let (name, device, softirq, values) = &rows[1];
+        assert_eq!(name, "24");
+        // 数字 IRQ 去掉中断控制器和触发方式
+        assert_eq!(device, "eth0-TxRx-0");
+        assert!(!softirq);
+        assert_eq!(values, &[123456, 7890]);
+        assert_eq!(rows[3].1, "Local timer interrupts");
+        // ERR 只有一个总数，其余 CPU 补 0
+        assert_eq!(rows[4].3, [0, 0]);
+    }
+
+    #[test]
+    fn parses_softirqs_table() {
+        let content = "                    CPU0       CPU1\n          HI:          1          2\n      NET_RX:        100        x\n";
+        let (cpus, rows) = parse_table(content, true).unwrap();
+        assert_eq!(cpus, 2);
+        assert_eq!(rows[0].0, "HI");
+        assert!(rows[0].2);
+        // 计数不是数字时从那里开始视为说明，缺少的计数补 0
+        assert_eq!(rows[1].3, [100, 0]);
+        assert_eq!(rows[1].1, "x");
+        assert!(parse_table("", false).is_none());
+    }
+
+    #[test]
+    fn parses_stat_totals() {
+        let content = "cpu 1 2 3 4\nintr 5000 22 0 9\nctxt 12345\nbtime 1700000000\nsoftirq 700 1 2 3\n";
+        assert_eq!(parse_stat(content), Some([12345, 5000, 700]));
+        // 缺少任一行或不是数字时无法使用
+        assert_eq!(parse_stat("intr 5000\nctxt 12345\n"), None);
+        assert_eq!(parse_stat("intr 5000\nctxt x\nsoftirq 1\n"), None);
+    }
+
+    #[test]
+    fn counters_going_backwards_give_zero_rate() {
+        assert_eq!(rate(300, 100, 2.0), 100.0);
+        // 32 位计数回绕
+        assert_eq!(rate(5, u32::MAX as u64 - 5, 1.0), 0.0);
+        assert_eq!(rate(300, 100, 0.0), 0.0);
+    }
+}
@@ -1,19 +1,53 @@
 // src/memory.rs
 
 use sysinfo::{System};
-use std::collections::VecDeque;
+use std::collections::HashMap;
+use std::fs;
+use std::time::{Duration, Instant};
+use crate::data::collector::{Collector, Metric, Reader, Refresh, Sampled, DEFAULT_HISTORY};
+use crate::data::series::{mix_u64, Mean, TimeSeries};
 
 // 用于存储内存的静态信息，类似 CoreInfo
 pub struct MemoryInfo {
     pub total_memory: u64,
 }
 
-// 负责跟踪内存使用率历史
+/// 一次采样的内存明细（字节）
+#[derive(Clone, Copy, Default, Debug)]
+pub struct MemorySample {
+    pub used: u64,      // 应用程序占用：总量减去空闲、缓冲、缓存和可回收的 slab
+    pub available: u64, // 可用内存（包括可回收的缓存）
+    pub free: u64,      // 完全空闲
+    pub buffers: u64,
+    pub cached: u64,
+    pub shared: u64,    // tmpfs / 共享内存，包含在 cached 中
+    pub dirty: u64,     // 等待写回磁盘的页面
+    pub slab: u64,      // 可回收的 slab（主要是 dentry 和 inode 缓存）；不可回收的部分计入 used
+    pub swap_used: u64,
+    pub swap_total: u64,
+}
+
+impl Mean for MemorySample {
+    fn mix(self, other: MemorySample, weight: f64) -> MemorySample {
+        MemorySample {
+            used: mix_u64(self.used, other.used, weight),
+            available: mix_u64(self.available, other.available, weight),
+            free: mix_u64(self.free, other.free, weight),
+            buffers: mix_u64(self.buffers, other.buffers, weight),
+            cached: mix_u64(self.cached, other.cached, weight),
+            shared: mix_u64(self.shared, other.shared, weight),
+            dirty: mix_u64(self.dirty, other.dirty, weight),
+            slab: mix_u64(self.slab, other.slab, weight),
+            swap_used: mix_u64(self.swap_used, other.swap_used, weight),
+            swap_total: mix_u64(self.swap_total, other.swap_total, weight),
+        }
+    }
+}
+
+// 负责跟踪内存明细历史
 pub struct Memory {
     pub info: MemoryInfo,
-    pub usages: VecDeque<f64>, // 内存使用率历史
-    pub max_history: usize,
-    system: System,
+    pub samples: TimeSeries<MemorySample>, // 内存明细历史
 }
 
 impl MemoryInfo {
@@ -24,40 +58,122 @@ impl MemoryInfo {
 }
 
 impl Memory {
-    pub fn new() -> Memory {
-        let mut system = System::new();
-        system.refresh_memory(); // 初始化时刷新一次内存信息
+    /// sys 需要已经刷新过内存信息
+    pub fn new(system: &System) -> Memory {
         let info = MemoryInfo {
             total_memory: system.total_memory(),
         };
 
         Memory {
             info,
-            usages: VecDeque::new(),
-            max_history: 60, // 内存历史可以设置长一点，比如60个点
-            system,
+            samples: TimeSeries::new(DEFAULT_HISTORY),
         }
     }
 
-    pub fn update(&mut self) {
-        // 1. 刷新内存数据
-        self.system.refresh_memory();
+    /// 最近一次采样的内存明细
+    pub fn latest(&self) -> MemorySample {
+        self.samples.latest().copied().unwrap_or_default()
+    }
+}
+
+/// Memory 的读取端，使用 sysinfo 的内存信息和 /proc/meminfo
+pub struct MemoryReader;
 
-        // 2. 计算内存使用率 (已用 / 总计) * 100
-        let used_memory = self.system.used_memory();
-        let total_memory = self.system.total_memory();
-        let usage = (used_memory as f64 / total_memory as f64) * 100.0;
+impl Reader for MemoryReader {
+    type Reading = MemorySample;
 
-        // 3. 更新使用率历史
-        self.usages.push_back(usage);
+    fn refresh(&self) -> Refresh {
+        Refresh { memory: true, ..Refresh::default() }
+    }
 
-        // 4. 如果历史记录超过最大长度，移除最旧的数据
-        if self.usages.len() > self.max_history {
-            self.usages.pop_front();
-        }
+    /// sys 需要已经刷新过内存信息
+    /// 组合 sysinfo 和 /proc/meminfo 的数据；无法读取 /proc/meminfo 时缓存等字段为 0
+    fn read(&mut self, system: &System) -> MemorySample {
+        read_sample(system)
+    }
+}
+
+fn read_sample(system: &System) -> MemorySample {
+    let total = system.total_memory();
+    let free = system.free_memory();
+    let meminfo = read_meminfo();
+    let get = |key: &str| meminfo.get(key).copied().unwrap_or(0);
+
+    let buffers = get("Buffers");
+    let cached = get("Cached");
+    // 可回收的 slab 像页面缓存一样可以释放；不可回收的部分 (SUnreclaim) 是内核真正占用的内存
+    let slab = get("SReclaimable");
+    let used = if meminfo.is_empty() {
+        system.used_memory()
+    } else {
+        total.saturating_sub(free + buffers + cached + slab)
+    };
+
+    MemorySample {
+        used,
+        available: system.available_memory(),
+        free,
+        buffers,
+        cached,
+        shared: get("Shmem"),
+        dirty: get("Dirty"),
+        slab,
+        swap_used: system.used_swap(),
+        swap_total: system.total_swap(),
+    }
+}
+
+/// 解析 /proc/meminfo，返回 名称 -> 字节数
+fn read_meminfo() -> HashMap<String, u64> {
+    let Ok(content) = fs::read_to_string("/proc/meminfo") else {
+        return HashMap::new();
+    };
+    content
+        .lines()
+        .filter_map(|line| {
+            let (key, rest) = line.split_once(':')?;
+            let mut parts = rest.split_whitespace();
+            let value: u64 = parts.next()?.parse().ok()?;
+            // 大部分字段以 kB 为单位，少数（如 HugePages_Total）没有单位
+            let bytes = if parts.next() == Some("kB") { value * 1024 } else { value };
+            Some((key.to_string(), bytes))
+        })
+        .collect()
+}
+
+const METRICS: &[Metric] = &[
+    Metric { name: "used", unit: "B", description: "应用程序占用的内存" },
+    Metric { name: "available", unit: "B", description: "可用内存（包括可回收的缓存）" },
+    Metric { name: "buffers", unit: "B", description: "块设备缓冲" },
+    Metric { name: "cached", unit: "B", description: "页面缓存" },
+    Metric { name: "slab", unit: "B", description: "可回收的内核 slab 缓存" },
+    Metric { name: "dirty", unit: "B", description: "等待写回磁盘的页面" },
+    Metric { name: "swap_used", unit: "B", description: "已用交换分区" },
+];
+
+impl Collector for Memory {
+    fn name(&self) -> &'static str {
+        "memory"
     }
 
+    fn metrics(&self) -> &'static [Metric] {
+        METRICS
+    }
 
+    fn set_history(&mut self, history: Duration) {
+        self.samples.set_window(history);
+    }
+}
 
+impl Sampled for Memory {
+    type Reader = MemoryReader;
 
+    fn reader(&self) -> MemoryReader {
+        MemoryReader
+    }
+
+    fn apply(&mut self, sample: MemorySample, at: Instant) {
+        // 超过历史长度的数据会被移除
+        self.samples.push(at, sample);
+    }
 }
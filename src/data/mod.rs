@@ -1,10 +1,124 @@
 // 修改模块声明
+pub mod collector;
+pub mod connections;
 pub mod core;
+pub mod cpu_time;
+pub mod disk;
+pub mod disk_io;
 pub mod gpu;
+pub mod host;
+pub mod interrupts;
 pub mod memory; // 新增
 pub mod net;
+pub mod pressure;
+pub mod process;
+pub mod sensors;
+pub mod series;
+pub mod summary;
+pub mod vmstat;
+
+use std::time::Duration;
 
 // 更新use语句\pub use core::Cores;
+pub use connections::Connections;
+pub use cpu_time::CpuTimes;
+pub use disk::DiskInfo;
+pub use disk_io::DiskIo;
+pub use host::HostInfo;
+pub use interrupts::Interrupts;
 pub use core::Cores;
 pub use memory::Memory; // 新增
-pub use net::NetInfo;
+pub use pressure::Pressure;
+pub use process::Processes;
+pub use sensors::Sensors;
+pub use summary::Summary;
+pub use vmstat::VmStat;
+
+/// 把字节数格式化为便于阅读的字符串，例如 "1.5G"
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "K", "M", "G", "T"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{}{}", bytes, UNITS[0])
+    } else {
+        format!("{:.1}{}", value, UNITS[unit])
+    }
+}
+
+/// 解析 "250ms"、"2s"、"5m"、"1h" 形式的时长，没有单位时按秒计算
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let value: f64 = number.parse().map_err(|_| format!("invalid duration {:?}, expected e.g. 500ms, 2s, 5m", text))?;
+    let seconds = match unit.trim() {
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" => value * 60.0,
+        "h" => value * 3600.0,
+        other => return Err(format!("unknown unit {:?} in {:?}, expected ms, s, m or h", other, text)),
+    };
+    // 数字部分只有数字和小数点，不会是负数或 NaN，转换失败只可能是超出范围
+    let duration = Duration::try_from_secs_f64(seconds).map_err(|_| format!("duration {:?} is too long", text))?;
+    if duration.is_zero() {
+        return Err(format!("duration {:?} must be positive", text));
+    }
+    Ok(duration)
+}
+
+/// 把时长格式化为最简短的形式，例如 "250ms"、"30s"、"5m"、"1h"
+pub fn format_duration(duration: Duration) -> String {
+    let millis = duration.as_millis();
+    match millis {
+        m if m % 3_600_000 == 0 && m > 0 => format!("{}h", m / 3_600_000),
+        m if m % 60_000 == 0 && m > 0 => format!("{}m", m / 60_000),
+        m if m % 1000 == 0 => format!("{}s", m / 1000),
+        m => format!("{}ms", m),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("2"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration(" 1.5s "), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn rejects_bad_durations() {
+        assert!(parse_duration("0s").unwrap_err().contains("must be positive"));
+        assert!(parse_duration(&format!("{}h", "9".repeat(30))).unwrap_err().contains("too long"));
+        assert!(parse_duration("5d").unwrap_err().contains("unknown unit"));
+        assert!(parse_duration("fast").unwrap_err().contains("invalid duration"));
+        assert!(parse_duration("-1s").unwrap_err().contains("invalid duration"));
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(Duration::from_millis(250)), "250ms");
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1500ms");
+        assert_eq!(format_duration(Duration::from_secs(30)), "30s");
+        assert_eq!(format_duration(Duration::from_secs(90)), "90s");
+        assert_eq!(format_duration(Duration::from_secs(300)), "5m");
+        assert_eq!(format_duration(Duration::from_secs(7200)), "2h");
+    }
+
+    #[test]
+    fn formatted_durations_parse_back() {
+        for millis in [100, 250, 1500, 30_000, 300_000, 3_600_000] {
+            let duration = Duration::from_millis(millis);
+            assert_eq!(parse_duration(&format_duration(duration)), Ok(duration));
+        }
+    }
+}
@@ -1,138 +1,489 @@
 // src/net.rs
 
-use std::collections::VecDeque;
+use std::collections::HashMap;
+use std::fs;
 use std::time::{Duration, Instant};
-use sysinfo::{System, Networks};
+use regex::Regex;
+use sysinfo::{NetworkData, Networks, System};
+use crate::data::collector::{Collector, Metric, Reader, Sampled, DEFAULT_HISTORY};
+use crate::data::series::TimeSeries;
 
-pub struct NetInfo {
-    pub interface_name: String,  // 选择的网络接口名称
+/// 接口的累计包计数：包数和错误来自 sysinfo，丢包和多播来自 /proc/net/dev
+#[derive(Clone, Copy, Default)]
+pub struct PacketCounters {
+    pub rx_packets: u64,
+    pub tx_packets: u64,
+    pub rx_errors: u64,
+    pub tx_errors: u64,
+    pub rx_drops: u64,
+    pub tx_drops: u64,
+    pub multicast: u64, // 收到的多播包
+}
+
+impl PacketCounters {
+    fn read(data: &NetworkData, dev: Option<&DevCounters>) -> PacketCounters {
+        let dev = dev.copied().unwrap_or_default();
+        PacketCounters {
+            rx_packets: data.total_packets_received(),
+            tx_packets: data.total_packets_transmitted(),
+            rx_errors: data.total_errors_on_received(),
+            tx_errors: data.total_errors_on_transmitted(),
+            rx_drops: dev.rx_drops,
+            tx_drops: dev.tx_drops,
+            multicast: dev.multicast,
+        }
+    }
+
+    pub fn errors(&self) -> u64 {
+        self.rx_errors + self.tx_errors
+    }
+
+    pub fn drops(&self) -> u64 {
+        self.rx_drops + self.tx_drops
+    }
+
+    /// 任一计数比上次小，说明计数器被重置
+    fn is_reset_from(&self, last: &PacketCounters) -> bool {
+        self.rx_packets < last.rx_packets
+            || self.tx_packets < last.tx_packets
+            || self.errors() < last.errors()
+            || self.drops() < last.drops()
+            || self.multicast < last.multicast
+    }
+}
+
+/// 每秒的包、错误、丢包和多播数
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct PacketRates {
+    pub rx_packets: f64,
+    pub tx_packets: f64,
+    pub errors: f64,
+    pub drops: f64,
+    pub multicast: f64,
+}
+
+impl PacketRates {
+    /// 根据前后两次累计计数计算速率；读取端已经排除了计数器重置，这里仍按 0 处理变小的计数
+    fn between(last: &PacketCounters, now: &PacketCounters, seconds: f64) -> PacketRates {
+        let rate = |new: u64, old: u64| new.saturating_sub(old) as f64 / seconds;
+        PacketRates {
+            rx_packets: rate(now.rx_packets, last.rx_packets),
+            tx_packets: rate(now.tx_packets, last.tx_packets),
+            errors: rate(now.errors(), last.errors()),
+            drops: rate(now.drops(), last.drops()),
+            multicast: rate(now.multicast, last.multicast),
+        }
+    }
+}
+
+/// /proc/net/dev 中 sysinfo 没有提供的计数
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+struct DevCounters {
+    rx_drops: u64,
+    tx_drops: u64,
+    multicast: u64,
+}
+
+/// 单个网络接口的流量统计
+pub struct InterfaceStats {
+    pub name: String,
     pub received: u64,           // 总接收字节数
     pub transmitted: u64,        // 总发送字节数
     pub download_rate: f64,      // 下载速率 (KB/s)
     pub upload_rate: f64,        // 上传速率 (KB/s)
-    pub download_rates: VecDeque<f64>, // 下载速率历史
-    pub upload_rates: VecDeque<f64>,   // 上传速率历史
-    pub last_updated: Instant,    // 上次更新时间
-    pub networks: Networks,
+    pub download_rates: TimeSeries<f64>, // 下载速率历史，NaN 表示计数器重置造成的缺口
+    pub upload_rates: TimeSeries<f64>,   // 上传速率历史，NaN 表示计数器重置造成的缺口
+    pub packets: PacketCounters,       // 累计包计数
+    pub packet_rates: PacketRates,     // 每秒包速率
 }
 
-impl NetInfo {
-    pub fn new() -> Self {
+impl InterfaceStats {
+    fn new(name: &str, received: u64, transmitted: u64, packets: PacketCounters, history: Duration) -> Self {
         Self {
-            interface_name: String::new(),
-            received: 0,
-            transmitted: 0,
+            name: name.to_string(),
+            received,
+            transmitted,
             download_rate: 0.0,
             upload_rate: 0.0,
-            download_rates: VecDeque::with_capacity(60),
-            upload_rates: VecDeque::with_capacity(60),
-            last_updated: Instant::now(),
-            networks: Networks::new(),
+            download_rates: TimeSeries::new(history),
+            upload_rates: TimeSeries::new(history),
+            packets,
+            packet_rates: PacketRates::default(),
+        }
+    }
+}
+
+pub struct NetInfo {
+    pub interfaces: Vec<InterfaceStats>, // 所有被跟踪的接口，按名称排序
+    pub interface_name: String,  // 图表中显示的网络接口名称
+    pub iface_filter: Option<Regex>, // --iface 指定的接口名称或正则表达式（需完整匹配）
+    pub history: Duration,       // 历史记录保留的时长
+}
+
+impl NetInfo {
+    pub fn new() -> Self {
+        Self {
+            interfaces: Vec::new(),
+            interface_name: "N/A".to_string(), // 第一次读取后选择
+            iface_filter: None,
+            history: DEFAULT_HISTORY,
         }
     }
 
-    /// 初始化网络接口
-pub fn initialize(&mut self) {
-    // 定义一些常见的主要接口关键词，不区分大小写
-    let primary_keywords = vec!["wlan", "eth", "en", "wl"];
-
-    // --- 第一步：尝试智能选择一个主要接口 ---
-    for (name, _) in self.networks.iter() {
-        let name_lower = name.to_lowercase();
-        for keyword in &primary_keywords {
-            if name_lower.contains(keyword) {
-                self.interface_name = name.to_string();
-                
-                // 找到接口后，立即初始化数据并返回
-                if let Some(data) = self.networks.get(&self.interface_name) {
-                    self.received = data.total_received();
-                    self.transmitted = data.total_transmitted();
-                    self.last_updated = Instant::now();
+    /// 选择默认显示的接口
+    fn select_default(&mut self) {
+        // 指定了 --iface 时直接使用第一个匹配的接口
+        if self.iface_filter.is_none() {
+            // 定义一些常见的主要接口关键词，不区分大小写
+            let primary_keywords = ["wlan", "eth", "en", "wl"];
+
+            // 尝试智能选择一个主要接口
+            for iface in &self.interfaces {
+                let name_lower = iface.name.to_lowercase();
+                if primary_keywords.iter().any(|keyword| name_lower.contains(keyword)) {
+                    self.interface_name = iface.name.clone();
+                    return; // 关键：找到后就退出函数
                 }
-                return; // 关键：找到后就退出函数
             }
         }
+
+        // 如果没找到主要接口，则回退到使用第一个接口
+        self.interface_name = self
+            .interfaces
+            .first()
+            .map(|i| i.name.clone())
+            .unwrap_or_else(|| "N/A".to_string()); // 给一个明确的提示
     }
 
-    // --- 第二步：如果没找到主要接口，则回退到使用第一个接口 ---
-    if let Some((name, data)) = self.networks.iter().next() {
-        self.interface_name = name.to_string();
-        
-        self.received = data.total_received();
-        self.transmitted = data.total_transmitted();
-        self.last_updated = Instant::now();
+    /// 图表中显示的接口
+    pub fn selected(&self) -> Option<&InterfaceStats> {
+        self.interfaces.iter().find(|i| i.name == self.interface_name)
     }
-    if self.interface_name.is_empty() {
-        self.interface_name = "N/A".to_string(); // 给一个明确的提示
+
+    /// 切换到下一个接口
+    pub fn select_next(&mut self) {
+        if self.interfaces.is_empty() {
+            return;
+        }
+        let index = self
+            .interfaces
+            .iter()
+            .position(|i| i.name == self.interface_name)
+            .map_or(0, |i| (i + 1) % self.interfaces.len());
+        self.interface_name = self.interfaces[index].name.clone();
+    }
+
+    /// 获取接口名称
+    pub fn get_interface_name(&self) -> &str {
+        &self.interface_name
     }
 
+    /// 把 --iface 的参数编译为完整匹配接口名称的正则表达式
+    pub fn parse_iface_filter(pattern: &str) -> Result<Regex, regex::Error> {
+        Regex::new(&format!("^(?:{})$", pattern))
+    }
 
+    /// 格式化速率显示
+    pub fn format_rate(rate: f64) -> String {
+        if rate < 1024.0 {
+            format!("{:.1} KB/s", rate)
+        } else if rate < 1024.0 * 1024.0 {
+            format!("{:.1} MB/s", rate / 1024.0)
+        } else {
+            format!("{:.1} GB/s", rate / (1024.0 * 1024.0))
+        }
+    }
 }
-    /// 更新网络信息
-    pub fn update(&mut self)->Result<(), String> {
-        if self.interface_name == "N/A" {
-            // 如果没有有效接口，直接返回
-            return Err("No valid network interface found".to_string());
+
+/// NetInfo 的读取端：持有 sysinfo 的 Networks，并保存每个接口上一次的计数
+pub struct NetReader {
+    networks: Networks,
+    iface_filter: Option<Regex>,
+    last: HashMap<String, (u64, u64, PacketCounters)>, // 接口名 -> (接收字节, 发送字节, 包计数)
+    last_updated: Instant,
+}
+
+/// 一个接口的速率：第一次出现时只有基线，计数器被重置时无法计算
+#[derive(PartialEq, Debug)]
+pub enum Rates {
+    Baseline,
+    Reset,
+    Measured { download: f64, upload: f64, packets: PacketRates },
+}
+
+impl Rates {
+    /// 根据上一次的 (接收字节, 发送字节, 包计数) 计算本周期的速率
+    fn between(
+        last: Option<&(u64, u64, PacketCounters)>,
+        received: u64,
+        transmitted: u64,
+        packets: &PacketCounters,
+        seconds: f64,
+    ) -> Rates {
+        match last {
+            // 新接口以当前计数为基线
+            None => Rates::Baseline,
+            // 计数器被重置（驱动重新加载、接口重建等），本次无法计算速率，以新的计数作为基线
+            Some((last_received, last_transmitted, last_packets))
+                if received < *last_received || transmitted < *last_transmitted || packets.is_reset_from(last_packets) =>
+            {
+                Rates::Reset
+            }
+            Some(_) if seconds <= 0.0 => Rates::Baseline,
+            // 计算速率 (KB/s)
+            Some((last_received, last_transmitted, last_packets)) => Rates::Measured {
+                download: (received - last_received) as f64 / seconds / 1024.0,
+                upload: (transmitted - last_transmitted) as f64 / seconds / 1024.0,
+                packets: PacketRates::between(last_packets, packets, seconds),
+            },
         }
+    }
+}
+
+/// 一次读取中的一个接口
+pub struct InterfaceReading {
+    name: String,
+    received: u64,
+    transmitted: u64,
+    packets: PacketCounters,
+    rates: Rates,
+}
+
+impl Reader for NetReader {
+    /// 当前所有被跟踪的接口
+    type Reading = Vec<InterfaceReading>;
 
+    fn read(&mut self, _sys: &System) -> Vec<InterfaceReading> {
+        // 接口可能被拔出（USB 网卡、VPN 断开）或新加入，每次都刷新列表
+        self.networks.refresh(true);
         let now = Instant::now();
-        let duration = now.duration_since(self.last_updated);
+        let seconds = now.duration_since(self.last_updated).as_secs_f64();
+        self.last_updated = now;
 
-        if let Some((_, network_data)) = self.networks.iter().find(|(name, _)| name == &self.interface_name.as_str()) {
-            let new_received = network_data.total_received();
-            let new_transmitted = network_data.total_transmitted();
+        // 非 Linux 系统上没有 /proc/net/dev，丢包和多播计数为 0
+        let dev = parse_net_dev(&fs::read_to_string("/proc/net/dev").unwrap_or_default());
+        let filter = &self.iface_filter;
+        let counters = self
+            .networks
+            .iter()
+            .filter(|(name, _)| filter.as_ref().is_none_or(|filter| filter.is_match(name)))
+            .map(|(name, data)| {
+                let packets = PacketCounters::read(data, dev.get(name));
+                (name.clone(), data.total_received(), data.total_transmitted(), packets)
+            });
+        measure(&mut self.last, counters, seconds)
+    }
+}
 
-            let delta_received = new_received.saturating_sub(self.received);
-            let delta_transmitted = new_transmitted.saturating_sub(self.transmitted);
+/// 根据每个接口上一次的计数计算速率，并把 last 替换为本次的计数；消失的接口从 last 中移除，
+/// 重新出现时从基线开始
+fn measure(
+    last: &mut HashMap<String, (u64, u64, PacketCounters)>,
+    counters: impl Iterator<Item = (String, u64, u64, PacketCounters)>,
+    seconds: f64,
+) -> Vec<InterfaceReading> {
+    let readings: Vec<InterfaceReading> = counters
+        .map(|(name, received, transmitted, packets)| {
+            let rates = Rates::between(last.get(&name), received, transmitted, &packets, seconds);
+            InterfaceReading { name, received, transmitted, packets, rates }
+        })
+        .collect();
+    *last = readings
+        .iter()
+        .map(|iface| (iface.name.clone(), (iface.received, iface.transmitted, iface.packets)))
+        .collect();
+    readings
+}
 
-            // 计算速率 (KB/s)
-            if duration.as_secs_f64() > 0.0 {
-                self.download_rate = (delta_received as f64 / duration.as_secs_f64()) / 1024.0;
-                self.upload_rate = (delta_transmitted as f64 / duration.as_secs_f64()) / 1024.0;
+/// 从 /proc/net/dev 的内容中取出每个接口的丢包和多播计数；列数不足或计数不是数字的行被跳过
+fn parse_net_dev(content: &str) -> HashMap<String, DevCounters> {
+    // 前两行是表头，之后每行为 "iface: rx_bytes rx_packets rx_errs rx_drop rx_fifo rx_frame
+    // rx_compressed rx_multicast tx_bytes tx_packets tx_errs tx_drop ..."
+    content
+        .lines()
+        .skip(2)
+        .filter_map(|line| {
+            let (name, rest) = line.split_once(':')?;
+            let fields: Vec<u64> = rest.split_whitespace().map(|v| v.parse().ok()).collect::<Option<_>>()?;
+            if fields.len() < 12 {
+                return None;
             }
+            let counters = DevCounters { rx_drops: fields[3], tx_drops: fields[11], multicast: fields[7] };
+            Some((name.trim().to_string(), counters))
+        })
+        .collect()
+}
 
-            // 更新历史数据
-            self.download_rates.push_back(self.download_rate);
-            self.upload_rates.push_back(self.upload_rate);
+const METRICS: &[Metric] = &[
+    Metric { name: "download", unit: "KB/s", description: "每个接口的下载速率" },
+    Metric { name: "upload", unit: "KB/s", description: "每个接口的上传速率" },
+    Metric { name: "packets", unit: "/s", description: "每个接口每秒收发的包数" },
+    Metric { name: "errors", unit: "/s", description: "每秒收发错误数" },
+    Metric { name: "drops", unit: "/s", description: "每秒丢包数" },
+    Metric { name: "multicast", unit: "/s", description: "每秒收到的多播包数" },
+];
 
-            if self.download_rates.len() > 60 {
-                self.download_rates.pop_front();
-            }
-            if self.upload_rates.len() > 60 {
-                self.upload_rates.pop_front();
+impl Collector for NetInfo {
+    fn name(&self) -> &'static str {
+        "net"
+    }
+
+    fn metrics(&self) -> &'static [Metric] {
+        METRICS
+    }
+
+    fn set_history(&mut self, history: Duration) {
+        self.history = history;
+        for iface in &mut self.interfaces {
+            iface.download_rates.set_window(history);
+            iface.upload_rates.set_window(history);
+        }
+    }
+}
+
+impl Sampled for NetInfo {
+    type Reader = NetReader;
+
+    fn reader(&self) -> NetReader {
+        NetReader {
+            networks: Networks::new(),
+            iface_filter: self.iface_filter.clone(),
+            last: HashMap::new(),
+            last_updated: Instant::now(),
+        }
+    }
+
+    /// 按读取结果增删接口，保留仍然存在的接口的历史；如果正在显示的接口消失了，重新选择一个
+    fn apply(&mut self, reading: Vec<InterfaceReading>, at: Instant) {
+        let mut previous = std::mem::take(&mut self.interfaces);
+        for new in reading {
+            let mut iface = match previous.iter().position(|iface| iface.name == new.name) {
+                Some(index) => previous.swap_remove(index),
+                None => InterfaceStats::new(&new.name, new.received, new.transmitted, new.packets, self.history),
+            };
+            match new.rates {
+                Rates::Baseline => {}
+                Rates::Reset => {
+                    // 在历史中记录一个缺口
+                    iface.download_rate = 0.0;
+                    iface.upload_rate = 0.0;
+                    iface.packet_rates = PacketRates::default();
+                    iface.download_rates.push(at, f64::NAN);
+                    iface.upload_rates.push(at, f64::NAN);
+                }
+                Rates::Measured { download, upload, packets } => {
+                    iface.download_rate = download;
+                    iface.upload_rate = upload;
+                    iface.packet_rates = packets;
+                    iface.download_rates.push(at, download);
+                    iface.upload_rates.push(at, upload);
+                }
             }
+            iface.received = new.received;
+            iface.transmitted = new.transmitted;
+            iface.packets = new.packets;
+            self.interfaces.push(iface);
+        }
+        self.interfaces.sort_by(|a, b| a.name.cmp(&b.name));
 
-            // 保存当前总值，供下次计算
-            self.received = new_received;
-            self.transmitted = new_transmitted;
-            self.last_updated = now;
+        if self.selected().is_none() {
+            self.select_default();
         }
-        Ok(())
     }
+}
 
-    /// 获取当前下载速率
-    pub fn current_download_rate(&self) -> f64 {
-        self.download_rates.back().copied().unwrap_or(0.0)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NET_DEV: &str = "\
+Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo:  123456     789    0    0    0     0          0         0   123456     789    0    0    0     0       0          0
+  eth0: 987654321 654321    1   12    0     0          0       345 12345678  54321    0    3    0     0       0          0
+wlan0:1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16
+";
+
+    #[test]
+    fn parses_drops_and_multicast() {
+        let dev = parse_net_dev(NET_DEV);
+        assert_eq!(dev.len(), 3);
+        assert_eq!(dev["eth0"], DevCounters { rx_drops: 12, tx_drops: 3, multicast: 345 });
+        assert_eq!(dev["lo"], DevCounters::default());
+        // 计数很大时冒号后面没有空格
+        assert_eq!(dev["wlan0"], DevCounters { rx_drops: 4, tx_drops: 12, multicast: 8 });
     }
 
-    /// 获取当前上传速率
-    pub fn current_upload_rate(&self) -> f64 {
-        self.upload_rates.back().copied().unwrap_or(0.0)
+    #[test]
+    fn skips_short_and_malformed_lines() {
+        let content = "header\nheader\n  eth0: 1 2 3\n  eth1: 1 2 3 x 5 6 7 8 9 10 11 12\n  eth2 1 2 3 4 5 6 7 8 9 10 11 12\n";
+        assert!(parse_net_dev(content).is_empty());
+        assert!(parse_net_dev("").is_empty());
     }
 
-    /// 获取接口名称
-    pub fn get_interface_name(&self) -> &str {
-        &self.interface_name
+    #[test]
+    fn packet_rates_never_go_negative() {
+        let older = PacketCounters { rx_packets: 100, tx_packets: 50, rx_drops: 5, multicast: 10, ..PacketCounters::default() };
+        let newer = PacketCounters { rx_packets: 300, tx_packets: 150, rx_drops: 7, multicast: 10, ..PacketCounters::default() };
+        let rates = PacketRates::between(&older, &newer, 2.0);
+        assert_eq!(rates.rx_packets, 100.0);
+        assert_eq!(rates.tx_packets, 50.0);
+        assert_eq!(rates.drops, 1.0);
+        assert_eq!(rates.multicast, 0.0);
+        // 参数顺序颠倒（计数器回绕）时按 0 计算
+        let rates = PacketRates::between(&newer, &older, 2.0);
+        assert_eq!(rates.rx_packets, 0.0);
+        assert_eq!(rates.drops, 0.0);
+        assert!(older.is_reset_from(&newer));
+        assert!(!newer.is_reset_from(&older));
     }
 
-    /// 格式化速率显示
-    pub fn format_rate(rate: f64) -> String {
-        if rate < 1024.0 {
-            format!("{:.1} KB/s", rate)
-        } else if rate < 1024.0 * 1024.0 {
-            format!("{:.1} MB/s", rate / 1024.0)
-        } else {
-            format!("{:.1} GB/s", rate / (1024.0 * 1024.0))
-        }
+    fn packets(rx: u64, tx: u64) -> PacketCounters {
+        PacketCounters { rx_packets: rx, tx_packets: tx, ..PacketCounters::default() }
+    }
+
+    #[test]
+    fn measures_rates_between_readings() {
+        let last = (1024, 2048, packets(10, 20));
+        let rates = Rates::between(Some(&last), 1024 + 4096, 2048 + 2048, &packets(30, 40), 2.0);
+        let Rates::Measured { download, upload, packets: packet_rates } = rates else {
+            panic!("expected measured rates");
+        };
+        assert_eq!(download, 2.0);
+        assert_eq!(upload, 1.0);
+        assert_eq!(packet_rates.rx_packets, 10.0);
+        // 第一次出现的接口和间隔为 0 时只建立基线
+        assert_eq!(Rates::between(None, 1, 1, &PacketCounters::default(), 1.0), Rates::Baseline);
+        assert_eq!(Rates::between(Some(&last), 2048, 4096, &packets(30, 40), 0.0), Rates::Baseline);
+    }
+
+    #[test]
+    fn counters_going_backwards_reset_the_baseline() {
+        let last = (1000, 1000, packets(10, 10));
+        assert_eq!(Rates::between(Some(&last), 500, 1500, &packets(20, 20), 1.0), Rates::Reset);
+        assert_eq!(Rates::between(Some(&last), 1500, 500, &packets(20, 20), 1.0), Rates::Reset);
+        assert_eq!(Rates::between(Some(&last), 1500, 1500, &packets(5, 20), 1.0), Rates::Reset);
+    }
+
+    #[test]
+    fn interfaces_coming_and_going_start_from_a_baseline() {
+        let mut last = HashMap::new();
+        let read = |last: &mut HashMap<_, _>, ifaces: &[(&str, u64)]| -> Vec<(String, Rates)> {
+            let counters = ifaces.iter().map(|&(name, bytes)| (name.to_string(), bytes, bytes, packets(bytes, bytes)));
+            measure(last, counters, 1.0).into_iter().map(|r| (r.name, r.rates)).collect()
+        };
+        let readings = read(&mut last, &[("eth0", 1024)]);
+        assert_eq!(readings[0].1, Rates::Baseline);
+        // 新接口 wlan0 出现：eth0 开始有速率，wlan0 只有基线
+        let readings = read(&mut last, &[("eth0", 2048), ("wlan0", 100)]);
+        assert!(matches!(readings[0].1, Rates::Measured { download: 1.0, .. }));
+        assert_eq!(readings[1].1, Rates::Baseline);
+        // eth0 消失后不再保留旧的计数
+        read(&mut last, &[("wlan0", 200)]);
+        assert!(!last.contains_key("eth0"));
+        // eth0 重新出现（计数可能已经重置），从基线开始而不是报告巨大的速率或 Reset
+        let readings = read(&mut last, &[("eth0", 10), ("wlan0", 300)]);
+        assert_eq!(readings[0].1, Rates::Baseline);
+        assert!(matches!(readings[1].1, Rates::Measured { .. }));
     }
 }
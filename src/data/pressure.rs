@@ -0,0 +1,172 @@
+// src/data/pressure.rs
+
+use std::fs;
+use std::time::{Duration, Instant};
+use crate::data::collector::{Collector, Metric, Reader, Sampled, DEFAULT_HISTORY};
+use crate::data::series::TimeSeries;
+use sysinfo::System;
+
+/// /proc/pressure/* 中的一行，avg 为最近 10 / 60 / 300 秒内任务因等待资源而停顿的时间占比 (%)
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct PressureLine {
+    pub avg10: f64,
+    pub avg60: f64,
+    pub avg300: f64,
+}
+
+/// 一种资源的压力
+pub struct ResourcePressure {
+    pub name: &'static str,
+    pub some: PressureLine,         // 至少有一个任务停顿
+    pub full: Option<PressureLine>, // 所有非空闲任务同时停顿；旧内核的 cpu 没有这一行
+    pub history: TimeSeries<f64>,   // some avg10 的历史
+}
+
+impl ResourcePressure {
+    fn new(name: &'static str) -> ResourcePressure {
+        ResourcePressure {
+            name,
+            some: PressureLine::default(),
+            full: None,
+            history: TimeSeries::new(DEFAULT_HISTORY),
+        }
+    }
+}
+
+/// 读取 Linux 的 Pressure Stall Information
+pub struct Pressure {
+    pub resources: Vec<ResourcePressure>, // cpu、memory、io
+    pub available: bool, // 内核不支持 PSI（4.20 之前，或未启用 CONFIG_PSI / psi=0）时为 false
+}
+
+impl Pressure {
+    pub fn new() -> Pressure {
+        Pressure {
+            resources: RESOURCES.into_iter().map(ResourcePressure::new).collect(),
+            available: false, // 第一次读取后更新
+        }
+    }
+}
+
+const RESOURCES: [&str; 3] = ["cpu", "memory", "io"];
+
+/// Pressure 的读取端，按 RESOURCES 的顺序读取 /proc/pressure/*
+pub struct PressureReader;
+
+impl Reader for PressureReader {
+    /// 每种资源的 some 和 full；无法读取的资源为 None
+    type Reading = Vec<Option<(PressureLine, Option<PressureLine>)>>;
+
+    fn read(&mut self, _sys: &System) -> Self::Reading {
+        RESOURCES
+            .iter()
+            .map(|name| parse_pressure(&fs::read_to_string(format!("/proc/pressure/{}", name)).ok()?))
+            .collect()
+    }
+}
+
+/// 解析一个 /proc/pressure/* 文件的 some 和 full 两行；没有有效的 some 行时返回 None
+fn parse_pressure(content: &str) -> Option<(PressureLine, Option<PressureLine>)> {
+    let mut some = None;
+    let mut full = None;
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            Some("some") => some = parse_line(fields),
+            Some("full") => full = parse_line(fields),
+            _ => {}
+        }
+    }
+    some.map(|some| (some, full))
+}
+
+/// 解析 "avg10=0.00 avg60=0.00 avg300=0.00 total=0"，忽略累计停顿时间 total；
+/// 缺少某个 avg 或不是数字时返回 None
+fn parse_line<'a>(fields: impl Iterator<Item = &'a str>) -> Option<PressureLine> {
+    let (mut avg10, mut avg60, mut avg300) = (None, None, None);
+    for field in fields {
+        match field.split_once('=') {
+            Some(("avg10", v)) => avg10 = v.parse().ok(),
+            Some(("avg60", v)) => avg60 = v.parse().ok(),
+            Some(("avg300", v)) => avg300 = v.parse().ok(),
+            _ => {}
+        }
+    }
+    Some(PressureLine { avg10: avg10?, avg60: avg60?, avg300: avg300? })
+}
+
+const METRICS: &[Metric] = &[
+    Metric { name: "cpu_some", unit: "%", description: "至少一个任务等待 CPU 的时间占比（avg10 / 60 / 300）" },
+    Metric { name: "memory_some", unit: "%", description: "至少一个任务等待内存的时间占比" },
+    Metric { name: "memory_full", unit: "%", description: "所有非空闲任务同时等待内存的时间占比" },
+    Metric { name: "io_some", unit: "%", description: "至少一个任务等待 I/O 的时间占比" },
+    Metric { name: "io_full", unit: "%", description: "所有非空闲任务同时等待 I/O 的时间占比" },
+];
+
+impl Collector for Pressure {
+    fn name(&self) -> &'static str {
+        "pressure"
+    }
+
+    fn metrics(&self) -> &'static [Metric] {
+        METRICS
+    }
+
+    fn set_history(&mut self, history: Duration) {
+        for resource in &mut self.resources {
+            resource.history.set_window(history);
+        }
+    }
+}
+
+impl Sampled for Pressure {
+    type Reader = PressureReader;
+
+    fn reader(&self) -> PressureReader {
+        PressureReader
+    }
+
+    fn apply(&mut self, reading: <PressureReader as Reader>::Reading, at: Instant) {
+        self.available = reading.iter().any(Option::is_some);
+        for (resource, lines) in self.resources.iter_mut().zip(reading) {
+            let Some((some, full)) = lines else {
+                continue;
+            };
+            resource.some = some;
+            resource.full = full;
+            resource.history.push(at, some.avg10);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_some_and_full() {
+        let content = "some avg10=1.50 avg60=0.75 avg300=0.10 total=123456\n\
+                       full avg10=0.50 avg60=0.25 avg300=0.00 total=45678\n";
+        let (some, full) = parse_pressure(content).unwrap();
+        assert_eq!(some, PressureLine { avg10: 1.5, avg60: 0.75, avg300: 0.1 });
+        assert_eq!(full, Some(PressureLine { avg10: 0.5, avg60: 0.25, avg300: 0.0 }));
+    }
+
+    #[test]
+    fn old_kernels_have_no_full_line_for_cpu() {
+        let (some, full) = parse_pressure("some avg10=3.00 avg60=2.00 avg300=1.00 total=99\n").unwrap();
+        assert_eq!(some.avg10, 3.0);
+        assert_eq!(full, None);
+    }
+
+    #[test]
+    fn rejects_short_and_malformed_lines() {
+        assert_eq!(parse_line("avg10=1.00 avg60=2.00".split_whitespace()), None);
+        assert_eq!(parse_line("avg10=1.00 avg60=x avg300=3.00".split_whitespace()), None);
+        // some 行无效时整个文件不可用，full 行无效时只忽略 full
+        assert_eq!(parse_pressure("some avg10=1.00\nfull avg10=0.00 avg60=0.00 avg300=0.00 total=0\n"), None);
+        let (_, full) = parse_pressure("some avg10=1.00 avg60=1.00 avg300=1.00 total=1\nfull garbage\n").unwrap();
+        assert_eq!(full, None);
+        assert_eq!(parse_pressure(""), None);
+    }
+}
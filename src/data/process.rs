@@ -0,0 +1,574 @@
+// src/data/process.rs
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System, ThreadKind, Users};
+use crate::data::collector::{Collector, Metric, Reader, Refresh, Sampled};
+
+/// 可以从界面发送给进程的信号
+pub const SIGNALS: [(Signal, &str); 4] = [
+    (Signal::Term, "SIGTERM"),
+    (Signal::Kill, "SIGKILL"),
+    (Signal::Stop, "SIGSTOP"),
+    (Signal::Continue, "SIGCONT"),
+];
+
+/// 向进程发送信号。共享的 System 在采样线程中，这里只刷新目标进程，确认它仍然存在
+pub fn send_signal(pid: u32, signal: Signal) -> Result<(), String> {
+    let pid = Pid::from_u32(pid);
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), false, ProcessRefreshKind::nothing());
+    let process = sys
+        .process(pid)
+        .ok_or_else(|| format!("Process {} no longer exists", pid))?;
+    match process.kill_with(signal) {
+        Some(true) => Ok(()),
+        Some(false) => Err(format!("Failed to send {:?} to {} (permission denied?)", signal, pid)),
+        None => Err(format!("{:?} is not supported on this platform", signal)),
+    }
+}
+
+/// 修改进程的 nice 值。直接调用 setpriority，不依赖 PATH 中的 renice 命令
+/// （BusyBox 的 renice -n 是相对当前值的增量，而这里设置的是绝对值）
+#[cfg(unix)]
+pub fn renice(pid: u32, nice: i32) -> Result<(), String> {
+    if !(-20..=19).contains(&nice) {
+        return Err(format!("Nice value {} out of range (-20..19)", nice));
+    }
+    // pid 为 0 表示调用者自己，不能从界面传进来
+    let target = i32::try_from(pid)
+        .ok()
+        .and_then(rustix::process::Pid::from_raw)
+        .ok_or_else(|| format!("Invalid PID {}", pid))?;
+    rustix::process::setpriority_process(Some(target), nice).map_err(|e| match e {
+        rustix::io::Errno::SRCH => format!("Process {} no longer exists", pid),
+        rustix::io::Errno::ACCESS | rustix::io::Errno::PERM => {
+            format!("Failed to set nice value of {} to {} (permission denied?)", pid, nice)
+        }
+        e => format!("renice failed: {}", e),
+    })
+}
+
+#[cfg(not(unix))]
+pub fn renice(_pid: u32, _nice: i32) -> Result<(), String> {
+    Err("Renice is not supported on this platform".to_string())
+}
+
+/// 进程表中的一行
+pub struct ProcessEntry {
+    pub pid: u32,
+    pub user: String,
+    pub cpu: f64,          // CPU 使用率 (%)，多核时可能超过 100
+    pub rss: u64,          // 常驻内存 (字节)
+    pub virtual_mem: u64,  // 虚拟内存 (字节)
+    pub state: String,
+    pub start_time: u64,   // 启动时间 (Unix 时间戳，秒)
+    pub command: String,
+    pub parent: Option<u32>,
+    // 以下字段由进程树计算得出
+    pub depth: usize,          // 在树中的深度，根节点为 0
+    pub has_children: bool,
+    pub subtree_cpu: f64,      // 自身及所有子孙进程的 CPU 使用率之和
+    pub subtree_rss: u64,      // 自身及所有子孙进程的常驻内存之和
+}
+
+/// 进程表可以按哪一列排序
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SortColumn {
+    Pid,
+    User,
+    Cpu,
+    Rss,
+    Virtual,
+    State,
+    Start,
+    Command,
+}
+
+impl SortColumn {
+    pub const ALL: [SortColumn; 8] = [
+        SortColumn::Pid,
+        SortColumn::User,
+        SortColumn::Cpu,
+        SortColumn::Rss,
+        SortColumn::Virtual,
+        SortColumn::State,
+        SortColumn::Start,
+        SortColumn::Command,
+    ];
+
+    /// 切换到下一列
+    pub fn next(self) -> SortColumn {
+        let index = SortColumn::ALL.iter().position(|&c| c == self).unwrap_or(0);
+        SortColumn::ALL[(index + 1) % SortColumn::ALL.len()]
+    }
+
+    /// 表头文字
+    pub fn title(self) -> &'static str {
+        match self {
+            SortColumn::Pid => "PID",
+            SortColumn::User => "USER",
+            SortColumn::Cpu => "CPU%",
+            SortColumn::Rss => "RSS",
+            SortColumn::Virtual => "VIRT",
+            SortColumn::State => "STATE",
+            SortColumn::Start => "START(UTC)",
+            SortColumn::Command => "COMMAND",
+        }
+    }
+
+    /// 树形模式下 CPU 和 RSS 按整棵子树的总和比较
+    fn compare(self, a: &ProcessEntry, b: &ProcessEntry, tree_mode: bool) -> Ordering {
+        match self {
+            SortColumn::Pid => a.pid.cmp(&b.pid),
+            SortColumn::User => a.user.cmp(&b.user),
+            SortColumn::Cpu if tree_mode => a.subtree_cpu.total_cmp(&b.subtree_cpu),
+            SortColumn::Cpu => a.cpu.total_cmp(&b.cpu),
+            SortColumn::Rss if tree_mode => a.subtree_rss.cmp(&b.subtree_rss),
+            SortColumn::Rss => a.rss.cmp(&b.rss),
+            SortColumn::Virtual => a.virtual_mem.cmp(&b.virtual_mem),
+            SortColumn::State => a.state.cmp(&b.state),
+            SortColumn::Start => a.start_time.cmp(&b.start_time),
+            SortColumn::Command => a.command.cmp(&b.command),
+        }
+    }
+}
+
+/// 进程列表：保存最近一次采样的所有进程，以及排序、过滤和选中状态
+pub struct Processes {
+    pub entries: Vec<ProcessEntry>, // 所有进程
+    pub visible: Vec<usize>,        // 过滤并排序后要显示的行（entries 的下标）
+    pub sort_by: SortColumn,
+    pub sort_desc: bool,
+    pub filter: String,
+    pub selected: usize, // 在 visible 中的位置
+    pub tree_mode: bool, // 按父子关系显示为进程树
+    collapsed: HashSet<u32>, // 树形模式下被折叠的进程 PID
+    children: HashMap<u32, Vec<usize>>, // PID -> 子进程在 entries 中的下标
+    roots: Vec<usize>, // 没有父进程（或父进程不可见）的进程
+}
+
+impl Processes {
+    pub fn new() -> Processes {
+        Processes {
+            entries: Vec::new(),
+            visible: Vec::new(),
+            sort_by: SortColumn::Cpu,
+            sort_desc: true,
+            filter: String::new(),
+            selected: 0,
+            tree_mode: false,
+            collapsed: HashSet::new(),
+            children: HashMap::new(),
+            roots: Vec::new(),
+        }
+    }
+
+    /// 写入新的进程列表，重新建立进程树并应用排序和过滤
+    fn update(&mut self, entries: Vec<ProcessEntry>) {
+        // 记住当前选中的 PID，刷新后继续选中它
+        let selected_pid = self.selected_entry().map(|e| e.pid);
+
+        self.entries = entries;
+        (self.roots, self.children) = build_tree(&mut self.entries);
+        // 已经退出的进程不再需要记住折叠状态
+        let entries = &self.entries;
+        self.collapsed.retain(|pid| entries.iter().any(|e| e.pid == *pid));
+        self.apply_view();
+        if let Some(pid) = selected_pid
+            && let Some(pos) = self.visible.iter().position(|&i| self.entries[i].pid == pid)
+        {
+            self.selected = pos;
+        }
+    }
+
+    fn matches_filter(&self, index: usize, filter: &str) -> bool {
+        let entry = &self.entries[index];
+        filter.is_empty()
+            || entry.command.to_lowercase().contains(filter)
+            || entry.user.to_lowercase().contains(filter)
+            || entry.pid.to_string().contains(filter)
+    }
+
+    /// 同级进程按当前排序方式排列
+    fn sort_indices(&self, indices: &mut [usize]) {
+        let entries = &self.entries;
+        indices.sort_by(|&a, &b| {
+            let ordering = self.sort_by.compare(&entries[a], &entries[b], self.tree_mode);
+            let ordering = if self.sort_desc { ordering.reverse() } else { ordering };
+            // 值相同时按 PID 排序，避免每次刷新时行的顺序跳动
+            ordering.then(entries[a].pid.cmp(&entries[b].pid))
+        });
+    }
+
+    /// 按当前的过滤条件和排序方式重新计算 visible
+    pub fn apply_view(&mut self) {
+        let filter = self.filter.to_lowercase();
+        self.visible = if self.tree_mode {
+            self.tree_view(&filter)
+        } else {
+            let mut visible: Vec<usize> = (0..self.entries.len())
+                .filter(|&i| self.matches_filter(i, &filter))
+                .collect();
+            self.sort_indices(&mut visible);
+            visible
+        };
+
+        if self.selected >= self.visible.len() {
+            self.selected = self.visible.len().saturating_sub(1);
+        }
+    }
+
+    /// 树形模式的行：先序遍历，跳过折叠节点的子孙；
+    /// 有过滤条件时保留匹配的进程及其祖先，以便看清它们所在的位置
+    fn tree_view(&self, filter: &str) -> Vec<usize> {
+        // 自底向上标记需要保留的节点
+        let mut keep = vec![filter.is_empty(); self.entries.len()];
+        if !filter.is_empty() {
+            let index_of: HashMap<u32, usize> =
+                self.entries.iter().enumerate().map(|(i, e)| (e.pid, i)).collect();
+            for i in 0..self.entries.len() {
+                if !self.matches_filter(i, filter) {
+                    continue;
+                }
+                let mut current = Some(i);
+                while let Some(c) = current {
+                    if keep[c] {
+                        break;
+                    }
+                    keep[c] = true;
+                    current = self.entries[c].parent.and_then(|ppid| index_of.get(&ppid).copied());
+                }
+            }
+        }
+
+        let mut roots = self.roots.clone();
+        self.sort_indices(&mut roots);
+        let mut visible = Vec::new();
+        let mut visited = vec![false; self.entries.len()];
+        let mut stack: Vec<usize> = roots.into_iter().rev().collect();
+        while let Some(i) = stack.pop() {
+            if !keep[i] || visited[i] {
+                continue;
+            }
+            visited[i] = true;
+            visible.push(i);
+            let pid = self.entries[i].pid;
+            if self.collapsed.contains(&pid) {
+                continue;
+            }
+            if let Some(kids) = self.children.get(&pid) {
+                let mut kids = kids.clone();
+                self.sort_indices(&mut kids);
+                stack.extend(kids.into_iter().rev());
+            }
+        }
+        visible
+    }
+
+    /// 指定的进程是否被折叠
+    pub fn is_collapsed(&self, pid: u32) -> bool {
+        self.collapsed.contains(&pid)
+    }
+
+    /// 切换列表 / 树形显示
+    pub fn toggle_tree_mode(&mut self) {
+        self.tree_mode = !self.tree_mode;
+        self.apply_view();
+    }
+
+    /// 折叠或展开选中的子树，collapse 为 None 时在两者之间切换
+    pub fn set_collapsed(&mut self, collapse: Option<bool>) {
+        if !self.tree_mode {
+            return;
+        }
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+        if !entry.has_children {
+            return;
+        }
+        let pid = entry.pid;
+        let collapse = collapse.unwrap_or(!self.collapsed.contains(&pid));
+        if collapse {
+            self.collapsed.insert(pid);
+        } else {
+            self.collapsed.remove(&pid);
+        }
+        self.apply_view();
+    }
+
+    /// 当前选中的进程
+    pub fn selected_entry(&self) -> Option<&ProcessEntry> {
+        self.visible.get(self.selected).map(|&i| &self.entries[i])
+    }
+
+    /// 上下移动选中行，delta 为负表示向上
+    pub fn move_selection(&mut self, delta: isize) {
+        if self.visible.is_empty() {
+            self.selected = 0;
+            return;
+        }
+        let last = self.visible.len() - 1;
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+
+    /// 切换到下一个排序列，并使用该列默认的排序方向
+    pub fn next_sort_column(&mut self) {
+        self.sort_by = self.sort_by.next();
+        // 数值列默认从大到小，文字列默认从小到大
+        self.sort_desc = matches!(
+            self.sort_by,
+            SortColumn::Cpu | SortColumn::Rss | SortColumn::Virtual | SortColumn::Start
+        );
+        self.apply_view();
+    }
+
+    /// 反转排序方向
+    pub fn toggle_sort_order(&mut self) {
+        self.sort_desc = !self.sort_desc;
+        self.apply_view();
+    }
+}
+
+const METRICS: &[Metric] = &[
+    Metric { name: "cpu", unit: "%", description: "每个进程的 CPU 使用率" },
+    Metric { name: "rss", unit: "B", description: "常驻内存" },
+    Metric { name: "virtual", unit: "B", description: "虚拟内存" },
+];
+
+/// Processes 的读取端：从已经刷新过的 System 中收集进程信息，用户名来自 /etc/passwd
+pub struct ProcessesReader {
+    users: Option<Users>, // 第一次读取时加载
+}
+
+impl Reader for ProcessesReader {
+    /// 所有进程
+    type Reading = Vec<ProcessEntry>;
+
+    fn refresh(&self) -> Refresh {
+        Refresh { process_details: true, ..Refresh::default() }
+    }
+
+    fn read(&mut self, sys: &System) -> Vec<ProcessEntry> {
+        let users = self.users.get_or_insert_with(Users::new_with_refreshed_list);
+        sys.processes()
+            .values()
+            // Linux 下线程也会出现在列表中，这里只保留进程
+            .filter(|p| p.thread_kind() != Some(ThreadKind::Userland))
+            .map(|p| {
+                let user = p
+                    .user_id()
+                    .map(|uid| match users.get_user_by_id(uid) {
+                        Some(user) => user.name().to_string(),
+                        None => uid.to_string(),
+                    })
+                    .unwrap_or_else(|| "-".to_string());
+                let command = if p.cmd().is_empty() {
+                    // 内核线程等没有命令行，用进程名代替
+                    format!("[{}]", p.name().to_string_lossy())
+                } else {
+                    p.cmd()
+                        .iter()
+                        .map(|arg| arg.to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                };
+                ProcessEntry {
+                    pid: p.pid().as_u32(),
+                    user,
+                    cpu: p.cpu_usage() as f64,
+                    rss: p.memory(),
+                    virtual_mem: p.virtual_memory(),
+                    state: p.status().to_string(),
+                    start_time: p.start_time(),
+                    command,
+                    parent: p.parent().map(|pid| pid.as_u32()),
+                    depth: 0,
+                    has_children: false,
+                    subtree_cpu: 0.0,
+                    subtree_rss: 0,
+                }
+            })
+            .collect()
+    }
+}
+
+/// 根据父 PID 建立进程树，并计算每个节点的深度和子树总量；返回 (根节点, PID -> 子进程下标)。
+/// 父进程已经退出或指向自己的进程作为根
+fn build_tree(entries: &mut [ProcessEntry]) -> (Vec<usize>, HashMap<u32, Vec<usize>>) {
+    let index_of: HashMap<u32, usize> = entries.iter().enumerate().map(|(i, e)| (e.pid, i)).collect();
+    let parent_of = |entry: &ProcessEntry| entry.parent.filter(|ppid| index_of.contains_key(ppid) && *ppid != entry.pid);
+
+    let mut children: HashMap<u32, Vec<usize>> = HashMap::new();
+    let mut roots = Vec::new();
+    for (i, entry) in entries.iter().enumerate() {
+        match parent_of(entry) {
+            Some(ppid) => children.entry(ppid).or_default().push(i),
+            None => roots.push(i),
+        }
+    }
+
+    let mut visited = vec![false; entries.len()];
+    for &root in &roots {
+        visit_subtree(entries, &children, root, &mut visited);
+    }
+    // PID 被复用时父子关系可能成环（A 的父进程是 B，B 的父进程又是 A），环上的进程从根出发到达不了。
+    // 沿父进程找到环上的一个进程，断开它和父进程的关系，作为根
+    while let Some(start) = visited.iter().position(|v| !v) {
+        let mut seen = HashSet::new();
+        let mut i = start;
+        while seen.insert(i) {
+            let Some(ppid) = parent_of(&entries[i]) else {
+                break;
+            };
+            i = index_of[&ppid];
+        }
+        if let Some(kids) = parent_of(&entries[i]).and_then(|ppid| children.get_mut(&ppid)) {
+            kids.retain(|&k| k != i);
+        }
+        roots.push(i);
+        visit_subtree(entries, &children, i, &mut visited);
+    }
+    (roots, children)
+}
+
+/// 深度优先遍历 root 的子树，先序记录深度，后序累加子树总量
+fn visit_subtree(entries: &mut [ProcessEntry], children: &HashMap<u32, Vec<usize>>, root: usize, visited: &mut [bool]) {
+    let mut stack = vec![(root, 0, false)];
+    while let Some((i, depth, children_done)) = stack.pop() {
+        let pid = entries[i].pid;
+        if children_done {
+            let (cpu, rss) = children.get(&pid).map_or((0.0, 0), |kids| {
+                kids.iter().fold((0.0, 0), |(cpu, rss), &k| (cpu + entries[k].subtree_cpu, rss + entries[k].subtree_rss))
+            });
+            let entry = &mut entries[i];
+            entry.subtree_cpu = entry.cpu + cpu;
+            entry.subtree_rss = entry.rss + rss;
+            continue;
+        }
+        if visited[i] {
+            continue;
+        }
+        visited[i] = true;
+        let kids = children.get(&pid);
+        entries[i].depth = depth;
+        entries[i].has_children = kids.is_some_and(|k| !k.is_empty());
+        stack.push((i, depth, true));
+        if let Some(kids) = kids {
+            stack.extend(kids.iter().map(|&k| (k, depth + 1, false)));
+        }
+    }
+}
+
+impl Collector for Processes {
+    fn name(&self) -> &'static str {
+        "processes"
+    }
+
+    fn metrics(&self) -> &'static [Metric] {
+        METRICS
+    }
+}
+
+impl Sampled for Processes {
+    type Reader = ProcessesReader;
+
+    fn reader(&self) -> ProcessesReader {
+        ProcessesReader { users: None }
+    }
+
+    fn apply(&mut self, entries: Vec<ProcessEntry>, _at: Instant) {
+        self.update(entries);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(pid: u32, parent: Option<u32>, cpu: f64, rss: u64) -> ProcessEntry {
+        ProcessEntry {
+            pid,
+            user: "root".to_string(),
+            cpu,
+            rss,
+            virtual_mem: 0,
+            state: "Sleeping".to_string(),
+            start_time: 0,
+            command: format!("proc{}", pid),
+            parent,
+            depth: 0,
+            has_children: false,
+            subtree_cpu: 0.0,
+            subtree_rss: 0,
+        }
+    }
+
+    #[test]
+    fn nests_children_and_sums_subtrees() {
+        let mut entries = vec![
+            entry(1, None, 1.0, 100),
+            entry(10, Some(1), 2.0, 200),
+            entry(11, Some(10), 3.0, 300),
+            entry(20, Some(1), 4.0, 400),
+        ];
+        let (roots, children) = build_tree(&mut entries);
+        assert_eq!(roots, [0]);
+        assert_eq!(children[&1], [1, 3]);
+        let depths: Vec<usize> = entries.iter().map(|e| e.depth).collect();
+        assert_eq!(depths, [0, 1, 2, 1]);
+        assert!(entries[0].has_children && entries[1].has_children && !entries[2].has_children);
+        assert_eq!(entries[0].subtree_cpu, 10.0);
+        assert_eq!(entries[0].subtree_rss, 1000);
+        assert_eq!(entries[1].subtree_rss, 500);
+        assert_eq!(entries[3].subtree_rss, 400);
+    }
+
+    #[test]
+    fn orphans_and_self_parents_become_roots() {
+        // 500 的父进程已经退出；PID 0 的父进程在部分平台上报告为自己
+        let mut entries = vec![entry(0, Some(0), 0.0, 10), entry(500, Some(499), 1.0, 20), entry(501, Some(500), 1.0, 30)];
+        let (roots, _) = build_tree(&mut entries);
+        assert_eq!(roots, [0, 1]);
+        assert_eq!(entries[0].subtree_rss, 10);
+        assert_eq!(entries[1].subtree_rss, 50);
+        assert_eq!(entries[2].depth, 1);
+    }
+
+    #[test]
+    fn breaks_parent_cycles_from_pid_reuse() {
+        // 100 和 200 互为父进程，300 挂在环上
+        let mut entries = vec![
+            entry(1, None, 0.0, 1),
+            entry(100, Some(200), 0.0, 10),
+            entry(200, Some(100), 0.0, 20),
+            entry(300, Some(200), 0.0, 30),
+        ];
+        let (roots, children) = build_tree(&mut entries);
+        // 100 被提升为根，200 和 300 依次挂在它下面
+        assert_eq!(roots, [0, 1]);
+        let depths: Vec<usize> = entries.iter().map(|e| e.depth).collect();
+        assert_eq!(depths, [0, 0, 1, 2]);
+        // 每个进程只计入一次
+        assert_eq!(entries[1].subtree_rss, 60);
+        let listed: usize = children.values().map(Vec::len).sum();
+        assert_eq!(listed + roots.len(), entries.len());
+    }
+
+    #[test]
+    fn tree_view_shows_every_process() {
+        let mut processes = Processes::new();
+        processes.tree_mode = true;
+        processes.update(vec![
+            entry(1, None, 0.0, 1),
+            entry(100, Some(200), 0.0, 10),
+            entry(200, Some(100), 0.0, 20),
+            entry(300, Some(999), 0.0, 30),
+        ]);
+        let mut pids: Vec<u32> = processes.visible.iter().map(|&i| processes.entries[i].pid).collect();
+        pids.sort();
+        assert_eq!(pids, [1, 100, 200, 300]);
+    }
+}
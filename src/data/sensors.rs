@@ -0,0 +1,175 @@
+// src/data/sensors.rs
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use sysinfo::{Components, System};
+use crate::data::collector::{Collector, Metric, Reader, Sampled, DEFAULT_HISTORY};
+use crate::data::series::TimeSeries;
+
+/// 传感器没有提供临界温度时使用的默认值 (°C)
+pub const DEFAULT_CRITICAL: f64 = 100.0;
+
+/// 单个温度传感器
+pub struct Sensor {
+    pub label: String,
+    key: SensorKey, // 在两次采样之间对应同一个传感器
+    pub temperature: Option<f64>, // 当前温度 (°C)，读取失败时为 None
+    pub max: Option<f64>,         // 最高温度 (°C)
+    pub critical: Option<f64>,    // 临界温度 (°C)
+    pub history: TimeSeries<f64>, // 温度历史
+}
+
+impl Sensor {
+    fn new(label: String, key: SensorKey, history: Duration) -> Sensor {
+        Sensor {
+            label,
+            key,
+            temperature: None,
+            max: None,
+            critical: None,
+            history: TimeSeries::new(history),
+        }
+    }
+
+    /// 当前温度占临界温度的比例，用于决定显示颜色
+    pub fn ratio(&self) -> Option<f64> {
+        let critical = self.critical.filter(|&c| c > 0.0).unwrap_or(DEFAULT_CRITICAL);
+        self.temperature.map(|t| t / critical)
+    }
+}
+
+/// 传感器的标识：sysinfo 的 id（hwmon 下为 "hwmon1_temp2" 这样的文件名）和标签，
+/// 再加上同样 id 和标签的第几个。两块 NVMe 都叫 "nvme Composite"，只按标签会把它们的历史混在一起
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+struct SensorKey {
+    id: Option<String>,
+    label: String,
+    nth: usize,
+}
+
+/// 通过 sysinfo 的 Components 读取所有温度传感器
+pub struct Sensors {
+    pub sensors: Vec<Sensor>, // 与 sysinfo 返回的顺序一致
+    pub history: Duration, // 历史记录保留的时长
+    pub read: bool, // 至少读取过一次，sensors 为空表示没有传感器而不是还没读取
+}
+
+impl Sensors {
+    pub fn new() -> Sensors {
+        Sensors {
+            sensors: Vec::new(), // 第一次读取后填入
+            history: DEFAULT_HISTORY,
+            read: false,
+        }
+    }
+
+    /// 是否可能有封装温度：还没读取过，或者读取到了。CPU 面板据此决定是否为徽标读取传感器
+    pub fn may_have_package(&self) -> bool {
+        !self.read || self.package().is_some()
+    }
+
+    /// CPU 封装温度。不同平台的传感器名称不同：Intel 为 "Package id 0"，
+    /// AMD 为 "Tctl"/"Tdie"，ARM 板卡通常只有 "cpu_thermal" 之类
+    pub fn package(&self) -> Option<&Sensor> {
+        let find = |keywords: &[&str]| {
+            self.sensors.iter().find(|s| {
+                let label = s.label.to_lowercase();
+                s.temperature.is_some() && keywords.iter().any(|k| label.contains(k))
+            })
+        };
+        find(&["package"]).or_else(|| find(&["tctl", "tdie"])).or_else(|| find(&["cpu", "coretemp", "k10temp"]))
+    }
+}
+
+/// Sensors 的读取端，持有 sysinfo 的 Components；读取 hwmon 可能很慢
+pub struct SensorsReader {
+    components: Components,
+}
+
+/// 一次读取中的一个传感器
+pub struct SensorReading {
+    key: SensorKey,
+    temperature: Option<f64>,
+    max: Option<f64>,
+    critical: Option<f64>,
+}
+
+impl Reader for SensorsReader {
+    /// 所有传感器，与 sysinfo 返回的顺序一致
+    type Reading = Vec<SensorReading>;
+
+    fn read(&mut self, _sys: &System) -> Vec<SensorReading> {
+        self.components.refresh(true);
+        let mut seen: HashMap<(Option<&str>, &str), usize> = HashMap::new();
+        self.components
+            .list()
+            .iter()
+            .map(|component| {
+                let nth = seen.entry((component.id(), component.label())).or_insert(0);
+                let key = SensorKey {
+                    id: component.id().map(str::to_string),
+                    label: component.label().to_string(),
+                    nth: *nth,
+                };
+                *nth += 1;
+                SensorReading {
+                    key,
+                    temperature: component.temperature().filter(|t| t.is_finite()).map(f64::from),
+                    max: component.max().filter(|t| t.is_finite()).map(f64::from),
+                    critical: component.critical().filter(|t| t.is_finite()).map(f64::from),
+                }
+            })
+            .collect()
+    }
+}
+
+const METRICS: &[Metric] = &[
+    Metric { name: "temperature", unit: "°C", description: "每个传感器的当前温度" },
+    Metric { name: "max", unit: "°C", description: "最高温度" },
+    Metric { name: "critical", unit: "°C", description: "临界温度" },
+];
+
+impl Collector for Sensors {
+    fn name(&self) -> &'static str {
+        "sensors"
+    }
+
+    fn metrics(&self) -> &'static [Metric] {
+        METRICS
+    }
+
+    fn set_history(&mut self, history: Duration) {
+        self.history = history;
+        for sensor in &mut self.sensors {
+            sensor.history.set_window(history);
+        }
+    }
+}
+
+impl Sampled for Sensors {
+    type Reader = SensorsReader;
+
+    fn reader(&self) -> SensorsReader {
+        SensorsReader { components: Components::new() }
+    }
+
+    fn apply(&mut self, reading: Vec<SensorReading>, at: Instant) {
+        self.read = true;
+        // 按标识沿用已有传感器的历史，新出现的传感器从本次开始记录
+        let mut previous: HashMap<SensorKey, Sensor> =
+            self.sensors.drain(..).map(|sensor| (sensor.key.clone(), sensor)).collect();
+        for new in reading {
+            let mut sensor = match previous.remove(&new.key) {
+                Some(sensor) => sensor,
+                None => Sensor::new(new.key.label.clone(), new.key, self.history),
+            };
+            sensor.temperature = new.temperature;
+            sensor.max = new.max;
+            sensor.critical = new.critical;
+            if let Some(temperature) = sensor.temperature {
+                sensor.history.push(at, temperature);
+            }
+            self.sensors.push(sensor);
+        }
+    }
+}
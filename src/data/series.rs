@@ -0,0 +1,203 @@
+// src/data/series.rs
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// 每个序列最多保留的点数，足够画满很宽的终端。历史很长或采样很快时，同一时间段内的多次采样合并为平均值，
+/// 所以内存和每帧的绘制开销都不随 --history / --interval 增长
+const CAPACITY: u32 = 600;
+
+/// 可以求平均的采样值，用于合并同一时间段内的多次采样
+pub trait Mean: Copy {
+    /// self 和 other 的加权平均，other 的权重为 weight (0..=1)
+    fn mix(self, other: Self, weight: f64) -> Self;
+}
+
+impl Mean for f64 {
+    /// NaN（缺口）参与合并后结果仍是 NaN，缺口不会被平均掉
+    fn mix(self, other: f64, weight: f64) -> f64 {
+        self + (other - self) * weight
+    }
+}
+
+/// 整数字段的加权平均
+pub fn mix_u64(a: u64, b: u64, weight: f64) -> u64 {
+    (a as f64).mix(b as f64, weight).round() as u64
+}
+
+/// 带时间戳的历史记录：按采样时间保存，只保留最近 window 时长内的采样。
+/// 采样间隔不均匀（间隔被修改、采样线程卡顿）时图表仍按真实时间绘制
+#[derive(Clone, Debug)]
+pub struct TimeSeries<T> {
+    points: VecDeque<(Instant, T, u32)>, // 时间、值、合并进来的采样数
+    latest: Option<T>,                   // 最近一次采样的原始值，不受合并影响
+    window: Duration,
+    origin: Instant, // 合并时划分时间段的起点
+}
+
+impl<T> TimeSeries<T> {
+    pub fn new(window: Duration) -> TimeSeries<T> {
+        TimeSeries { points: VecDeque::new(), latest: None, window, origin: Instant::now() }
+    }
+
+    /// 修改保留的时长，缩短时立即移除过旧的采样
+    pub fn set_window(&mut self, window: Duration) {
+        self.window = window;
+        self.trim();
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    fn trim(&mut self) {
+        let Some(&(newest, _, _)) = self.points.back() else {
+            return;
+        };
+        while self.points.front().is_some_and(|&(at, _, _)| newest.duration_since(at) > self.window) {
+            self.points.pop_front();
+        }
+    }
+
+    /// 移除所有采样
+    pub fn clear(&mut self) {
+        self.points.clear();
+        self.latest = None;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// 所有点的时间和值，最旧的在前
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Instant, &T)> + ExactSizeIterator {
+        self.points.iter().map(|(at, value, _)| (*at, value))
+    }
+
+    /// 所有点的值，最旧的在前；计算最大值等不需要时间的地方使用
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.points.iter().map(|(_, value, _)| value)
+    }
+
+    /// 最近一次采样的值
+    pub fn latest(&self) -> Option<&T> {
+        self.latest.as_ref()
+    }
+
+    /// 转换为图表的点：x 为采样时间相对 now 的秒数（过去为负数），y 由 value 取出。
+    /// 点数超过 max_points（图表能分辨的点数）时按时间分段取平均
+    pub fn points(&self, now: Instant, max_points: usize, value: impl Fn(&T) -> f64) -> Vec<(f64, f64)> {
+        if self.points.len() <= max_points {
+            return self.points.iter().map(|(at, v, _)| (seconds_before(now, *at), value(v))).collect();
+        }
+        self.slots(now, max_points, value).into_iter().flatten().collect()
+    }
+
+    /// 把 [now - window, now] 均匀分成 count 段，每段是落在其中的采样的平均值，没有采样的段为 None。
+    /// 迷你图每列取一段，这样不管面板宽度和采样间隔如何，显示的都是同样的时间范围
+    pub fn buckets(&self, now: Instant, count: usize, value: impl Fn(&T) -> f64) -> Vec<Option<f64>> {
+        self.slots(now, count, value).into_iter().map(|slot| slot.map(|(_, y)| y)).collect()
+    }
+
+    /// buckets 的实现，同时返回每段内采样时间的平均值作为 x
+    fn slots(&self, now: Instant, count: usize, value: impl Fn(&T) -> f64) -> Vec<Option<(f64, f64)>> {
+        let window = self.window.as_secs_f64();
+        if count == 0 || window <= 0.0 {
+            return Vec::new();
+        }
+        let mut sums = vec![(0.0, 0.0, 0u32); count];
+        for (at, v, n) in &self.points {
+            let x = seconds_before(now, *at);
+            if x < -window {
+                continue;
+            }
+            let index = (((x + window) / window * count as f64) as usize).min(count - 1);
+            let sum = &mut sums[index];
+            sum.0 += x * *n as f64;
+            sum.1 += value(v) * *n as f64;
+            sum.2 += n;
+        }
+        sums.into_iter()
+            .map(|(x, y, n)| (n > 0).then(|| (x / n as f64, y / n as f64)))
+            .collect()
+    }
+}
+
+impl<T: Mean> TimeSeries<T> {
+    /// 追加一次采样，移除比它早 window 以上的采样。
+    /// 和上一个点落在同一个时间段（window / CAPACITY）时合并到上一个点中
+    pub fn push(&mut self, at: Instant, value: T) {
+        self.latest = Some(value);
+        let resolution = (self.window / CAPACITY).as_nanos().max(1);
+        let slot = |at: Instant| at.saturating_duration_since(self.origin).as_nanos() / resolution;
+        match self.points.back_mut() {
+            Some((last_at, last, count)) if slot(*last_at) == slot(at) => {
+                *count += 1;
+                *last = last.mix(value, 1.0 / *count as f64);
+                *last_at = at;
+            }
+            _ => self.points.push_back((at, value, 1)),
+        }
+        self.trim();
+    }
+}
+
+/// 图表 x 轴的坐标：at 相对 now 的秒数，过去为负数
+pub fn seconds_before(now: Instant, at: Instant) -> f64 {
+    -now.saturating_duration_since(at).as_secs_f64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trims_samples_older_than_window() {
+        let start = Instant::now();
+        let mut series = TimeSeries::new(Duration::from_secs(10));
+        for second in 0..=20 {
+            series.push(start + Duration::from_secs(second), second as f64);
+        }
+        let values: Vec<f64> = series.values().copied().collect();
+        assert_eq!(values, (10..=20).map(f64::from).collect::<Vec<_>>());
+        assert_eq!(series.latest(), Some(&20.0));
+
+        series.set_window(Duration::from_secs(2));
+        assert_eq!(series.values().copied().collect::<Vec<_>>(), vec![18.0, 19.0, 20.0]);
+    }
+
+    #[test]
+    fn merges_samples_in_the_same_slot() {
+        let start = Instant::now();
+        let mut series = TimeSeries::new(Duration::from_secs(600));
+        // 每个时间段 1 秒，同一秒内的采样合并为平均值
+        series.push(start, 1.0);
+        series.push(start + Duration::from_millis(1), 2.0);
+        series.push(start + Duration::from_millis(2), 6.0);
+        assert_eq!(series.values().copied().collect::<Vec<_>>(), vec![3.0]);
+        assert_eq!(series.latest(), Some(&6.0));
+        // 下一个时间段开始新的点
+        series.push(start + Duration::from_secs(2), 4.0);
+        assert_eq!(series.values().copied().collect::<Vec<_>>(), vec![3.0, 4.0]);
+    }
+
+    #[test]
+    fn keeps_at_most_capacity_points() {
+        let start = Instant::now();
+        let mut series = TimeSeries::new(Duration::from_secs(60));
+        for millis in (0..120_000).step_by(10) {
+            series.push(start + Duration::from_millis(millis), 1.0);
+        }
+        assert!(series.values().len() <= CAPACITY as usize + 1);
+    }
+
+    #[test]
+    fn buckets_cover_the_window() {
+        let start = Instant::now();
+        let mut series = TimeSeries::new(Duration::from_secs(10));
+        series.push(start + Duration::from_secs(9), 5.0);
+        let now = start + Duration::from_secs(10);
+        let buckets = series.buckets(now, 5, |v| *v);
+        assert_eq!(buckets, vec![None, None, None, None, Some(5.0)]);
+    }
+}
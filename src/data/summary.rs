@@ -0,0 +1,142 @@
+// src/data/summary.rs
+
+use std::fs;
+use std::time::{Duration, Instant};
+use sysinfo::{ProcessStatus, System, ThreadKind};
+use crate::data::collector::{Collector, Metric, Reader, Refresh, Sampled, DEFAULT_HISTORY};
+use crate::data::series::{mix_u64, Mean, TimeSeries};
+
+/// 一次采样的负载和进程数量
+#[derive(Clone, Copy, Default)]
+pub struct SummarySample {
+    pub load: [f64; 3],      // 1 / 5 / 15 分钟平均负载
+    pub procs_running: u64,  // 可运行（正在运行或等待 CPU）的线程数
+    pub procs_blocked: u64,  // 等待 I/O 而阻塞的线程数
+    pub processes: usize,
+    pub threads: usize,
+    pub zombies: usize,
+}
+
+impl Mean for SummarySample {
+    fn mix(self, other: SummarySample, weight: f64) -> SummarySample {
+        SummarySample {
+            load: [0, 1, 2].map(|i| self.load[i].mix(other.load[i], weight)),
+            procs_running: mix_u64(self.procs_running, other.procs_running, weight),
+            procs_blocked: mix_u64(self.procs_blocked, other.procs_blocked, weight),
+            processes: mix_u64(self.processes as u64, other.processes as u64, weight) as usize,
+            threads: mix_u64(self.threads as u64, other.threads as u64, weight) as usize,
+            zombies: mix_u64(self.zombies as u64, other.zombies as u64, weight) as usize,
+        }
+    }
+}
+
+/// 系统概况：平均负载、运行队列和进程数量
+pub struct Summary {
+    pub samples: TimeSeries<SummarySample>,
+    pub run_queue_available: bool, // 非 Linux 或无法读取 /proc/stat 时为 false
+}
+
+impl Summary {
+    pub fn new() -> Summary {
+        Summary {
+            samples: TimeSeries::new(DEFAULT_HISTORY),
+            run_queue_available: false,
+        }
+    }
+
+    /// 最近一次采样的结果
+    pub fn latest(&self) -> SummarySample {
+        self.samples.latest().copied().unwrap_or_default()
+    }
+}
+
+/// Summary 的读取端，使用 sysinfo 的进程列表和 /proc/stat
+pub struct SummaryReader;
+
+impl Reader for SummaryReader {
+    /// 本次的汇总，以及是否读到了 procs_running / procs_blocked
+    type Reading = (SummarySample, bool);
+
+    fn refresh(&self) -> Refresh {
+        Refresh { processes: true, ..Refresh::default() }
+    }
+
+    fn read(&mut self, sys: &System) -> (SummarySample, bool) {
+        let load = System::load_average();
+        let mut sample = SummarySample {
+            load: [load.one, load.five, load.fifteen],
+            ..SummarySample::default()
+        };
+
+        // 用户态线程也出现在进程列表中，它们只计入线程数
+        for process in sys.processes().values() {
+            sample.threads += 1;
+            if process.thread_kind() == Some(ThreadKind::Userland) {
+                continue;
+            }
+            sample.processes += 1;
+            if process.status() == ProcessStatus::Zombie {
+                sample.zombies += 1;
+            }
+        }
+
+        let run_queue = read_run_queue();
+        if let Some((running, blocked)) = run_queue {
+            sample.procs_running = running;
+            sample.procs_blocked = blocked;
+        }
+        (sample, run_queue.is_some())
+    }
+}
+
+/// 读取 /proc/stat 中的 procs_running 和 procs_blocked
+fn read_run_queue() -> Option<(u64, u64)> {
+    let content = fs::read_to_string("/proc/stat").ok()?;
+    let mut running = None;
+    let mut blocked = None;
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        match (fields.next(), fields.next().and_then(|v| v.parse().ok())) {
+            (Some("procs_running"), Some(value)) => running = Some(value),
+            (Some("procs_blocked"), Some(value)) => blocked = Some(value),
+            _ => {}
+        }
+    }
+    Some((running?, blocked?))
+}
+
+const METRICS: &[Metric] = &[
+    Metric { name: "load", unit: "", description: "1 / 5 / 15 分钟平均负载" },
+    Metric { name: "procs_running", unit: "", description: "可运行的线程数" },
+    Metric { name: "procs_blocked", unit: "", description: "等待 I/O 而阻塞的线程数" },
+    Metric { name: "processes", unit: "", description: "进程数" },
+    Metric { name: "threads", unit: "", description: "线程数" },
+    Metric { name: "zombies", unit: "", description: "僵尸进程数" },
+];
+
+impl Collector for Summary {
+    fn name(&self) -> &'static str {
+        "summary"
+    }
+
+    fn metrics(&self) -> &'static [Metric] {
+        METRICS
+    }
+
+    fn set_history(&mut self, history: Duration) {
+        self.samples.set_window(history);
+    }
+}
+
+impl Sampled for Summary {
+    type Reader = SummaryReader;
+
+    fn reader(&self) -> SummaryReader {
+        SummaryReader
+    }
+
+    fn apply(&mut self, (sample, run_queue_available): (SummarySample, bool), at: Instant) {
+        self.run_queue_available = run_queue_available;
+        self.samples.push(at, sample);
+    }
+}
@@ -0,0 +1,247 @@
+// src/data/vmstat.rs
+
+use std::collections::HashMap;
+use std::fs;
+use std::time::{Duration, Instant};
+use crate::data::collector::{Collector, Metric, Reader, Sampled, DEFAULT_HISTORY};
+use crate::data::series::{Mean, TimeSeries};
+use sysinfo::System;
+
+/// /proc/vmstat 中关心的累计计数
+#[derive(Clone, Copy, Default)]
+struct Counters {
+    pswpin: u64,     // 从交换分区换入的页数
+    pswpout: u64,    // 换出到交换分区的页数
+    pgpgin: u64,     // 从磁盘读入的 KB 数
+    pgpgout: u64,    // 写回磁盘的 KB 数
+    pgfault: u64,    // 所有缺页异常（包括主缺页）
+    pgmajfault: u64, // 需要读磁盘的主缺页异常
+    oom_kill: u64,   // OOM killer 杀死的进程数
+}
+
+/// 一个采样周期内每秒的分页、交换和缺页次数
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct VmStatSample {
+    pub swap_in: f64,      // 页/秒
+    pub swap_out: f64,     // 页/秒
+    pub page_in: f64,      // KB/秒
+    pub page_out: f64,     // KB/秒
+    pub major_faults: f64, // 次/秒
+    pub minor_faults: f64, // 次/秒
+    pub oom_kills: f64,    // 次/秒
+}
+
+impl Mean for VmStatSample {
+    fn mix(self, other: VmStatSample, weight: f64) -> VmStatSample {
+        VmStatSample {
+            swap_in: self.swap_in.mix(other.swap_in, weight),
+            swap_out: self.swap_out.mix(other.swap_out, weight),
+            page_in: self.page_in.mix(other.page_in, weight),
+            page_out: self.page_out.mix(other.page_out, weight),
+            major_faults: self.major_faults.mix(other.major_faults, weight),
+            minor_faults: self.minor_faults.mix(other.minor_faults, weight),
+            oom_kills: self.oom_kills.mix(other.oom_kills, weight),
+        }
+    }
+}
+
+impl VmStatSample {
+    /// 根据前后两次累计计数计算每秒的次数；计数变小（回绕或重置）时该项按 0 计算
+    fn between(last: &Counters, counters: &Counters, seconds: f64) -> VmStatSample {
+        let rate = |cur: u64, prev: u64| cur.saturating_sub(prev) as f64 / seconds;
+        let major_faults = rate(counters.pgmajfault, last.pgmajfault);
+        VmStatSample {
+            swap_in: rate(counters.pswpin, last.pswpin),
+            swap_out: rate(counters.pswpout, last.pswpout),
+            page_in: rate(counters.pgpgin, last.pgpgin),
+            page_out: rate(counters.pgpgout, last.pgpgout),
+            major_faults,
+            minor_faults: (rate(counters.pgfault, last.pgfault) - major_faults).max(0.0),
+            oom_kills: rate(counters.oom_kill, last.oom_kill),
+        }
+    }
+}
+
+/// 读取 /proc/vmstat，计算分页、交换、缺页和 OOM kill 的速率
+pub struct VmStat {
+    pub samples: TimeSeries<VmStatSample>,
+    pub oom_kills: u64,  // 开机以来 OOM killer 杀死的进程总数
+    pub available: bool, // 非 Linux 或无法读取 /proc/vmstat 时为 false
+}
+
+impl VmStat {
+    pub fn new() -> VmStat {
+        VmStat {
+            samples: TimeSeries::new(DEFAULT_HISTORY),
+            oom_kills: 0,
+            available: false, // 第一次读取后更新
+        }
+    }
+
+    /// 最近一次采样的结果
+    pub fn latest(&self) -> VmStatSample {
+        self.samples.latest().copied().unwrap_or_default()
+    }
+}
+
+/// VmStat 的读取端，保存上一次的计数和读取时间
+pub struct VmStatReader {
+    last: Option<(Counters, Instant)>,
+}
+
+/// 一次读取的结果；无法读取 /proc/vmstat 时整个读取为 None
+pub struct VmStatReading {
+    sample: Option<VmStatSample>, // 第一次读取只建立基线
+    oom_kills: u64,
+}
+
+impl Reader for VmStatReader {
+    type Reading = Option<VmStatReading>;
+
+    fn read(&mut self, _sys: &System) -> Option<VmStatReading> {
+        let counters = parse_vmstat(&fs::read_to_string("/proc/vmstat").ok()?)?;
+        let now = Instant::now();
+        let sample = self.last.and_then(|(last, last_updated)| {
+            let seconds = now.duration_since(last_updated).as_secs_f64();
+            (seconds > 0.0).then(|| VmStatSample::between(&last, &counters, seconds))
+        });
+        // 间隔为 0 时保留原来的基线
+        if sample.is_some() || self.last.is_none() {
+            self.last = Some((counters, now));
+        }
+        Some(VmStatReading { sample, oom_kills: counters.oom_kill })
+    }
+}
+
+/// 解析 /proc/vmstat 中的 "名称 值" 行，值不是数字的行被忽略；
+/// 连所有内核都有的 pgfault 都没有时不是有效的 vmstat，返回 None
+fn parse_vmstat(content: &str) -> Option<Counters> {
+    let values: HashMap<&str, u64> = content
+        .lines()
+        .filter_map(|line| {
+            let (name, value) = line.split_once(' ')?;
+            Some((name, value.trim().parse().ok()?))
+        })
+        .collect();
+    let get = |name: &str| values.get(name).copied().unwrap_or(0);
+    values.contains_key("pgfault").then(|| Counters {
+        pswpin: get("pswpin"),
+        pswpout: get("pswpout"),
+        pgpgin: get("pgpgin"),
+        pgpgout: get("pgpgout"),
+        pgfault: get("pgfault"),
+        pgmajfault: get("pgmajfault"),
+        // oom_kill 从 Linux 4.13 开始才有
+        oom_kill: get("oom_kill"),
+    })
+}
+
+const METRICS: &[Metric] = &[
+    Metric { name: "swap_in", unit: "pages/s", description: "从交换分区换入的页数" },
+    Metric { name: "swap_out", unit: "pages/s", description: "换出到交换分区的页数" },
+    Metric { name: "page_in", unit: "KB/s", description: "从磁盘读入的数据量" },
+    Metric { name: "page_out", unit: "KB/s", description: "写回磁盘的数据量" },
+    Metric { name: "major_faults", unit: "/s", description: "需要读磁盘的主缺页异常" },
+    Metric { name: "minor_faults", unit: "/s", description: "次缺页异常" },
+    Metric { name: "oom_kills", unit: "", description: "OOM killer 杀死的进程数" },
+];
+
+impl Collector for VmStat {
+    fn name(&self) -> &'static str {
+        "vmstat"
+    }
+
+    fn metrics(&self) -> &'static [Metric] {
+        METRICS
+    }
+
+    fn set_history(&mut self, history: Duration) {
+        self.samples.set_window(history);
+    }
+
+    fn clear_history(&mut self) {
+        self.samples.clear();
+    }
+}
+
+impl Sampled for VmStat {
+    type Reader = VmStatReader;
+
+    fn reader(&self) -> VmStatReader {
+        VmStatReader { last: None }
+    }
+
+    fn apply(&mut self, reading: Option<VmStatReading>, at: Instant) {
+        self.available = reading.is_some();
+        let Some(reading) = reading else {
+            return;
+        };
+        self.oom_kills = reading.oom_kills;
+        if let Some(sample) = reading.sample {
+            self.samples.push(at, sample);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VMSTAT: &str = "\
+nr_free_pages 123456
+pgpgin 1000
+pgpgout 2000
+pswpin 10
+pswpout 20
+pgfault 50000
+pgmajfault 300
+oom_kill 2
+";
+
+    #[test]
+    fn parses_counters() {
+        let counters = parse_vmstat(VMSTAT).unwrap();
+        assert_eq!(counters.pgpgin, 1000);
+        assert_eq!(counters.pswpout, 20);
+        assert_eq!(counters.pgfault, 50000);
+        assert_eq!(counters.pgmajfault, 300);
+        assert_eq!(counters.oom_kill, 2);
+    }
+
+    #[test]
+    fn old_kernels_have_no_oom_kill() {
+        let counters = parse_vmstat("pgfault 10\npgmajfault 1\n").unwrap();
+        assert_eq!(counters.oom_kill, 0);
+        assert_eq!(counters.pswpin, 0);
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let counters = parse_vmstat("pgfault 10\npswpin x\npswpout\n pgpgin 5\n").unwrap();
+        assert_eq!(counters.pswpin, 0);
+        assert_eq!(counters.pswpout, 0);
+        assert_eq!(counters.pgpgin, 0);
+        assert!(parse_vmstat("").is_none());
+        assert!(parse_vmstat("nr_free_pages 1\n").is_none());
+    }
+
+    #[test]
+    fn computes_rates_between_samples() {
+        let last = parse_vmstat(VMSTAT).unwrap();
+        let counters = Counters { pswpin: 30, pgfault: 51000, pgmajfault: 500, oom_kill: 3, ..last };
+        let sample = VmStatSample::between(&last, &counters, 2.0);
+        assert_eq!(sample.swap_in, 10.0);
+        assert_eq!(sample.swap_out, 0.0);
+        assert_eq!(sample.major_faults, 100.0);
+        // pgfault 包含主缺页
+        assert_eq!(sample.minor_faults, 400.0);
+        assert_eq!(sample.oom_kills, 0.5);
+    }
+
+    #[test]
+    fn counters_going_backwards_give_zero_rates() {
+        let newer = parse_vmstat(VMSTAT).unwrap();
+        let older = Counters { pgpgin: 500, pgfault: 40000, pgmajfault: 100, ..newer };
+        assert_eq!(VmStatSample::between(&newer, &older, 1.0), VmStatSample::default());
+    }
+}
@@ -3,39 +3,105 @@
 mod app;
 mod ui;
 mod data;
-use clap::Parser;
+mod modules;
+mod sampler;
+use clap::{error::ErrorKind, parser::ValueSource, Arg, ArgAction, ArgMatches, Command, CommandFactory, FromArgMatches, Parser};
 use std::io;
+use std::time::Duration;
 
-use app::App;
+use app::{App, SystemInfo};
+use modules::{Module, MODULES};
+use sampler::Sampler;
+use data::parse_duration;
 
 /// 一个简单的终端系统监控工具
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, next_display_order = 100)]
 struct Cli {
-    // ... Cli 结构体定义保持不变 ...
-    #[arg(short = 'c', long)]
-    cpu: bool,
-    #[arg(short = 'm', long)]
-    memory: bool,
+    // 各模块的开关（-c、-m、-n ...）和模块自己的参数（--per-core、--iface ...）由 modules::MODULES 生成，见 module_args
+    /// GPU 模块尚未实现
     #[arg(short = 'g', long)]
     gpu: bool,
-    #[arg(short = 'n', long)]
-    net: bool,
+    /// 采样间隔，例如 "500ms"、"2s"（运行时按 '[' / ']' 调整）
+    #[arg(long, value_name = "DURATION", default_value = "250ms", value_parser = parse_duration)]
+    interval: Duration,
+    /// 所有图表显示的历史长度，例如 "30s"、"5m"（运行时按 '{' / '}' 调整）
+    #[arg(long, value_name = "DURATION", default_value = "1m", value_parser = parse_duration)]
+    history: Duration,
+    /// 列出每个模块的命令行参数、采集器和指标后退出
+    #[arg(long)]
+    list_metrics: bool,
+}
+
+/// 最短的采样间隔，更短时采样线程几乎一直占着 CPU
+const MIN_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 为注册表中的每个模块生成 --<name> / -<short> 开关，以及模块自己的参数（Module::args）
+fn module_args(command: Command) -> Command {
+    // 模块开关排在其它参数前面，模块自己的参数紧随其后
+    let switches = MODULES.iter().enumerate().map(|(order, module)| {
+        let arg = Arg::new(module.name)
+            .long(module.name)
+            .help(module.help)
+            .action(ArgAction::SetTrue)
+            .display_order(order);
+        match module.short {
+            Some(short) => arg.short(short),
+            None => arg,
+        }
+    });
+    let options = MODULES
+        .iter()
+        .flat_map(|module| (module.args)())
+        .enumerate()
+        .map(|(order, arg)| arg.display_order(MODULES.len() + order));
+    command.args(switches).args(options)
+}
+
+/// 命令行中是否给出了模块自己的参数
+fn has_module_args(module: &Module, matches: &ArgMatches) -> bool {
+    (module.args)().iter().any(|arg| matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine))
+}
+
+/// 命令行中选择的模块；--per-core、--vmstat 等视图参数隐含启用所属的模块（Module::args_imply）
+fn selected_modules(matches: &ArgMatches) -> Vec<&'static str> {
+    MODULES
+        .iter()
+        .filter(|module| matches.get_flag(module.name) || (module.args_imply && has_module_args(module, matches)))
+        .map(|module| module.name)
+        .collect()
 }
 
 fn main() -> io::Result<()> {
     // 1. 解析命令行参数
-    let cli = Cli::parse();
+    let matches = module_args(Cli::command()).get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    if cli.interval < MIN_INTERVAL {
+        let message = format!("--interval must be at least {}ms", MIN_INTERVAL.as_millis());
+        module_args(Cli::command()).error(ErrorKind::ValueValidation, message).exit();
+    }
+    if cli.history < cli.interval {
+        let message = "--history must not be shorter than --interval";
+        module_args(Cli::command()).error(ErrorKind::ArgumentConflict, message).exit();
+    }
+    if cli.list_metrics {
+        modules::print_metrics(&SystemInfo::new(Sampler::new().system()));
+        return Ok(());
+    }
 
     // 2. 决定显示哪些模块
-    let show_all = !cli.cpu && !cli.memory && !cli.gpu && !cli.net;
+    let selected = selected_modules(&matches);
+    let show_all = selected.is_empty() && !cli.gpu;
 
     // 3. 创建 App 实例
     let mut app = if show_all {
         App::default()
     } else {
-        App::new(cli.cpu, cli.memory, cli.gpu, cli.net)
+        App::new(selected)
     };
+    app.set_timing(cli.interval, cli.history);
+    // 采样线程在 run() 中才启动，模块对采集器的修改不会和采样交错
+    app.configure(&matches);
 
     // 4. 运行应用
     app.run()?;
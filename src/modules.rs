@@ -0,0 +1,436 @@
+// src/modules.rs
+
+use crate::app::{App, SystemInfo};
+use clap::{Arg, ArgAction, ArgMatches};
+use crossterm::event::KeyCode;
+use crate::data::net::NetInfo;
+use crate::ui::connections::draw_connections;
+use crate::ui::draw;
+use crate::ui::interrupts::draw_interrupts;
+use crate::ui::process::{self, draw_action_popup, draw_process_table, InputMode, ProcessView};
+use ratatui::{layout::Rect, Frame};
+use regex::Regex;
+use std::any::Any;
+
+/// 一个显示模块：命令行参数、采样线程要刷新的采集器和界面中的面板都由这里生成
+pub struct Module {
+    pub name: &'static str,                  // 命令行长参数 --<name>
+    pub short: Option<char>,                 // 命令行短参数
+    pub help: &'static str,                  // 命令行帮助
+    pub collectors: &'static [&'static str], // 显示时需要刷新的采集器（Collector::name）
+    pub needs: fn(&App, &str) -> bool,       // collectors 中的采集器在当前视图下是否需要刷新，不显示的数据不读取；每轮采样后重新判断
+    pub state: fn() -> Box<dyn Any>,         // 模块的界面状态（视图切换等），用 App::state 按类型取出
+    pub args: fn() -> Vec<Arg>,              // 模块自己的命令行参数，排在模块开关之后
+    pub args_imply: bool,                    // 给出 args 中的参数时隐含启用本模块
+    pub configure: fn(&mut App, &ArgMatches), // 把 args 的值写入界面状态或采集器，在启动采样线程之前调用
+    pub keys: fn(&App) -> String,            // 底部提示栏中的快捷键说明，没有时为空
+    pub on_key: fn(&mut App, KeyCode) -> bool, // 处理模块的快捷键，不是它的按键时返回 false
+    pub modal: fn(&App) -> bool,             // 正在输入或显示弹出窗口：所有按键（包括 'q'）只交给本模块，提示栏只显示它的说明
+    pub bottom: bool,                        // 占据图表下方的区域，而不是放进网格
+    pub draw: fn(&mut Frame, Rect, &App, &SystemInfo), // 绘制时持有采集器的锁
+    pub overlay: fn(&mut Frame, &App, &SystemInfo), // 所有面板画完后绘制，用于覆盖在界面上的弹出窗口
+}
+
+/// CPU 面板的显示方式
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CpuView {
+    #[default]
+    Average,   // 所有核心的平均使用率
+    PerCore,   // 每个逻辑核心一个小图
+    Breakdown, // user / system / iowait / steal 等时间的堆叠图
+    Frequency, // 每个核心的频率历史
+}
+
+impl CpuView {
+    /// 切换到下一种显示方式
+    pub fn next(self) -> CpuView {
+        match self {
+            CpuView::Average => CpuView::PerCore,
+            CpuView::PerCore => CpuView::Breakdown,
+            CpuView::Breakdown => CpuView::Frequency,
+            CpuView::Frequency => CpuView::Average,
+        }
+    }
+}
+
+/// 内存模块的界面状态
+#[derive(Default)]
+pub struct MemoryView {
+    pub vmstat: bool, // 内存面板下方显示分页和交换速率
+}
+
+/// 网络模块的界面状态
+#[derive(Default)]
+pub struct NetView {
+    pub table: bool, // 以表格形式同时显示所有接口
+}
+
+/// 套接字模块的界面状态
+#[derive(Default)]
+pub struct SocketsView {
+    pub listening: bool, // 显示监听端口和事件日志，而不是所有套接字
+}
+
+/// 所有视图都用到全部采集器的模块
+fn all_collectors(_: &App, _: &str) -> bool {
+    true
+}
+
+/// 没有界面状态的模块
+fn no_state() -> Box<dyn Any> {
+    Box::new(())
+}
+
+/// 没有自己的命令行参数的模块
+fn no_args() -> Vec<Arg> {
+    Vec::new()
+}
+
+/// 没有命令行参数要处理的模块
+fn no_configure(_: &mut App, _: &ArgMatches) {}
+
+/// 提示栏中没有快捷键说明的模块
+fn no_help(_: &App) -> String {
+    String::new()
+}
+
+/// 没有快捷键的模块
+fn no_keys(_: &mut App, _: KeyCode) -> bool {
+    false
+}
+
+/// 从不独占键盘的模块
+fn never_modal(_: &App) -> bool {
+    false
+}
+
+/// 没有弹出窗口的模块
+fn no_overlay(_: &mut Frame, _: &App, _: &SystemInfo) {}
+
+/// 开关类的命令行参数
+fn flag(id: &'static str, help: &'static str) -> Arg {
+    Arg::new(id).long(id).help(help).action(ArgAction::SetTrue)
+}
+
+/// 所有模块，顺序就是界面中的绘制顺序。新增模块时实现 Collector、在 app.rs 的 system_info! 中登记采集器，
+/// 再在这里登记即可，命令行参数、界面状态、采样线程、按键分发、提示栏和布局不需要修改
+pub const MODULES: &[Module] = &[
+    Module {
+        name: "cpu",
+        short: Some('c'),
+        help: "显示 CPU 使用率（运行时按 'c' 在平均 / 每核心 / 时间分解 / 频率视图之间切换）",
+        collectors: &["cpu", "cpu_times", "sensors"],
+        needs: |app, collector| match collector {
+            "cpu_times" => *app.state::<CpuView>() == CpuView::Breakdown,
+            // 右上角的封装温度徽标；第一次读取没有找到封装温度传感器后就不再读取
+            "sensors" => app.info().sensors.may_have_package(),
+            _ => true,
+        },
+        state: || Box::new(CpuView::default()),
+        args: || {
+            vec![
+                flag("per-core", "CPU 面板按核心分别显示（运行时按 'c' 切换）")
+                    .conflicts_with_all(["cpu-breakdown", "cpu-freq"]),
+                flag("cpu-breakdown", "CPU 面板显示 user / system / iowait / steal 等时间的堆叠图")
+                    .conflicts_with("cpu-freq"),
+                flag("cpu-freq", "CPU 面板显示每个核心的频率历史、最高频率和 scaling governor"),
+            ]
+        },
+        args_imply: true,
+        configure: |app, matches| {
+            let view = app.state_mut::<CpuView>();
+            if matches.get_flag("per-core") {
+                *view = CpuView::PerCore;
+            } else if matches.get_flag("cpu-breakdown") {
+                *view = CpuView::Breakdown;
+            } else if matches.get_flag("cpu-freq") {
+                *view = CpuView::Frequency;
+            }
+        },
+        keys: |_| "'c' CPU view (average / per-core / breakdown / frequency).".into(),
+        on_key: |app, code| match code {
+            KeyCode::Char('c') => {
+                let view = app.state_mut::<CpuView>();
+                *view = view.next();
+                true
+            }
+            _ => false,
+        },
+        modal: never_modal,
+        bottom: false,
+        draw: |f, area, app, info| draw::draw_cpu_panel(f, area, *app.state::<CpuView>(), info),
+        overlay: no_overlay,
+    },
+    Module {
+        name: "memory",
+        short: Some('m'),
+        help: "显示内存占用（运行时按 'v' 显示分页和交换面板）",
+        collectors: &["memory", "vmstat"],
+        needs: |app, collector| collector != "vmstat" || app.state::<MemoryView>().vmstat,
+        state: || Box::new(MemoryView::default()),
+        args: || {
+            vec![flag(
+                "vmstat",
+                "在内存面板下方显示换入/换出、页面读写、缺页异常和 OOM kill 速率（运行时按 'v' 切换，隐含 -m）",
+            )]
+        },
+        args_imply: true,
+        configure: |app, matches| app.state_mut::<MemoryView>().vmstat = matches.get_flag("vmstat"),
+        keys: |_| "'v' paging/swap panel.".into(),
+        on_key: |app, code| match code {
+            KeyCode::Char('v') => {
+                let view = app.state_mut::<MemoryView>();
+                view.vmstat = !view.vmstat;
+                true
+            }
+            _ => false,
+        },
+        modal: never_modal,
+        bottom: false,
+        draw: |f, area, app, info| draw::draw_memory_panel(f, area, app.state::<MemoryView>().vmstat, info),
+        overlay: no_overlay,
+    },
+    Module {
+        name: "pressure",
+        short: Some('P'),
+        help: "显示 cpu / memory / io 的 Pressure Stall Information（/proc/pressure）",
+        collectors: &["pressure"],
+        needs: all_collectors,
+        state: no_state,
+        args: no_args,
+        args_imply: false,
+        configure: no_configure,
+        keys: no_help,
+        on_key: no_keys,
+        modal: never_modal,
+        bottom: false,
+        draw: |f, area, _, info| draw::draw_pressure(f, area, &info.pressure),
+        overlay: no_overlay,
+    },
+    Module {
+        name: "load",
+        short: Some('l'),
+        help: "显示平均负载、运行队列、进程/线程数和僵尸进程数",
+        collectors: &["summary"],
+        needs: all_collectors,
+        state: no_state,
+        args: no_args,
+        args_imply: false,
+        configure: no_configure,
+        keys: no_help,
+        on_key: no_keys,
+        modal: never_modal,
+        bottom: false,
+        draw: |f, area, _, info| draw::draw_summary(f, area, &info.summary, &info.cores),
+        overlay: no_overlay,
+    },
+    Module {
+        name: "net",
+        short: Some('n'),
+        help: "显示网络流量",
+        collectors: &["net"],
+        needs: all_collectors,
+        state: || Box::new(NetView::default()),
+        args: || {
+            vec![Arg::new("iface")
+                .long("iface")
+                .value_name("NAME|REGEX")
+                .value_parser(NetInfo::parse_iface_filter)
+                .help("只跟踪名称匹配的网络接口（接口名或正则表达式，需完整匹配，例如 \"eth0\" 或 \"bond.*\"）")]
+        },
+        args_imply: false,
+        configure: |app, matches| app.info().net.iface_filter = matches.get_one::<Regex>("iface").cloned(),
+        keys: |_| "'i' next interface, 'I' interface table.".into(),
+        on_key: |app, code| match code {
+            KeyCode::Char('i') => {
+                app.info().net.select_next();
+                true
+            }
+            KeyCode::Char('I') => {
+                let view = app.state_mut::<NetView>();
+                view.table = !view.table;
+                true
+            }
+            _ => false,
+        },
+        modal: never_modal,
+        bottom: false,
+        draw: |f, area, app, info| draw::draw_net(f, area, &info.net, app.state::<NetView>().table),
+        overlay: no_overlay,
+    },
+    Module {
+        name: "disk",
+        short: Some('d'),
+        help: "显示文件系统的空间和 inode 使用情况",
+        collectors: &["disk"],
+        needs: all_collectors,
+        state: no_state,
+        args: || {
+            vec![Arg::new("disk-fs")
+                .long("disk-fs")
+                .value_name("FS")
+                .value_delimiter(',')
+                .action(ArgAction::Append)
+                .help("磁盘面板中额外显示的文件系统类型，默认忽略 tmpfs、overlay、squashfs 等；\"all\" 表示全部显示")]
+        },
+        args_imply: false,
+        configure: |app, matches| {
+            app.info().disk.include_fs = matches.get_many::<String>("disk-fs").into_iter().flatten().cloned().collect();
+        },
+        keys: no_help,
+        on_key: no_keys,
+        modal: never_modal,
+        bottom: false,
+        draw: |f, area, _, info| draw::draw_disk_table(f, area, &info.disk),
+        overlay: no_overlay,
+    },
+    Module {
+        name: "io",
+        short: Some('i'),
+        help: "显示块设备的 I/O 吞吐量、IOPS、平均等待时间和利用率",
+        collectors: &["disk_io"],
+        needs: all_collectors,
+        state: no_state,
+        args: no_args,
+        args_imply: false,
+        configure: no_configure,
+        keys: |_| "'b' next block device.".into(),
+        on_key: |app, code| match code {
+            KeyCode::Char('b') => {
+                app.info().disk_io.select_next();
+                true
+            }
+            _ => false,
+        },
+        modal: never_modal,
+        bottom: false,
+        draw: |f, area, _, info| draw::draw_disk_io(f, area, &info.disk_io),
+        overlay: no_overlay,
+    },
+    Module {
+        name: "sockets",
+        short: Some('s'),
+        help: "显示 TCP/UDP 套接字列表和各连接状态的数量",
+        collectors: &["connections"],
+        needs: all_collectors,
+        state: || Box::new(SocketsView::default()),
+        args: no_args,
+        args_imply: false,
+        configure: no_configure,
+        keys: |_| "'l' listening ports.".into(),
+        on_key: |app, code| match code {
+            KeyCode::Char('l') => {
+                let view = app.state_mut::<SocketsView>();
+                view.listening = !view.listening;
+                true
+            }
+            _ => false,
+        },
+        modal: never_modal,
+        bottom: false,
+        draw: |f, area, app, info| {
+            draw_connections(f, area, &info.connections, app.state::<SocketsView>().listening)
+        },
+        overlay: no_overlay,
+    },
+    Module {
+        name: "temp",
+        short: Some('t'),
+        help: "显示温度传感器的当前、最高和临界温度以及温度历史",
+        collectors: &["sensors"],
+        needs: all_collectors,
+        state: no_state,
+        args: no_args,
+        args_imply: false,
+        configure: no_configure,
+        keys: no_help,
+        on_key: no_keys,
+        modal: never_modal,
+        bottom: false,
+        draw: |f, area, _, info| draw::draw_sensors(f, area, &info.sensors),
+        overlay: no_overlay,
+    },
+    Module {
+        name: "irq",
+        short: None,
+        help: "显示上下文切换、硬中断和软中断速率，以及每个 IRQ 在各 CPU 上的分布（/proc/interrupts、/proc/softirqs）",
+        collectors: &["interrupts"],
+        needs: all_collectors,
+        state: no_state,
+        args: no_args,
+        args_imply: false,
+        configure: no_configure,
+        keys: no_help,
+        on_key: no_keys,
+        modal: never_modal,
+        bottom: false,
+        draw: |f, area, _, info| draw_interrupts(f, area, &info.interrupts),
+        overlay: no_overlay,
+    },
+    Module {
+        name: "proc",
+        short: Some('p'),
+        help: "显示进程列表",
+        collectors: &["processes"],
+        needs: all_collectors,
+        state: || Box::new(ProcessView::default()),
+        args: || vec![flag("read-only", "只读模式：禁止从界面向进程发送信号或修改 nice 值")],
+        args_imply: false,
+        configure: |app, matches| app.state_mut::<ProcessView>().read_only = matches.get_flag("read-only"),
+        keys: process::keys,
+        on_key: process::on_key,
+        modal: |app| app.state::<ProcessView>().input != InputMode::Normal,
+        bottom: true,
+        draw: |f, area, app, info| {
+            let editing_filter = app.state::<ProcessView>().input == InputMode::Filter;
+            draw_process_table(f, area, &info.processes, editing_filter);
+        },
+        overlay: |f, app, info| draw_action_popup(f, &app.state::<ProcessView>().input, &info.processes),
+    },
+];
+
+/// 按名称查找模块
+pub fn find(name: &str) -> Option<&'static Module> {
+    MODULES.iter().find(|module| module.name == name)
+}
+
+/// 检查每个模块声明的采集器都已登记。名称写错时采样线程会一直跳过它、面板永远没有数据，所以启动时就报错
+pub fn check_collectors(info: &SystemInfo) {
+    let collectors = info.collectors();
+    for module in MODULES {
+        for name in module.collectors {
+            assert!(
+                collectors.iter().any(|c| c.name() == *name),
+                "module {:?} uses unknown collector {:?}",
+                module.name,
+                name
+            );
+        }
+    }
+}
+
+/// --list-metrics：列出每个模块的命令行参数、采集器和指标
+pub fn print_metrics(info: &SystemInfo) {
+    check_collectors(info);
+    let collectors = info.collectors();
+    for module in MODULES {
+        match module.short {
+            Some(short) => println!("-{}, --{}  {}", short, module.name, module.help),
+            None => println!("--{}  {}", module.name, module.help),
+        }
+        for arg in (module.args)() {
+            let help = arg.get_help().map(|help| help.to_string()).unwrap_or_default();
+            println!("    --{}  {}", arg.get_id(), help);
+        }
+        for name in module.collectors {
+            let Some(collector) = collectors.iter().find(|c| c.name() == *name) else {
+                continue;
+            };
+            for metric in collector.metrics() {
+                let unit = if metric.unit.is_empty() { String::new() } else { format!(" ({})", metric.unit) };
+                println!("    {}.{}{}  {}", name, metric.name, unit, metric.description);
+            }
+        }
+        println!();
+    }
+}
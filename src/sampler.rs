@@ -0,0 +1,142 @@
+// src/sampler.rs
+
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+use sysinfo::{CpuRefreshKind, System};
+use crate::app::SystemInfo;
+use crate::data::collector::{Reader, Refresh};
+
+/// 锁住共享的采集器；采样线程 panic 时数据仍然可读，不让界面跟着崩溃
+pub fn lock(info: &Mutex<SystemInfo>) -> MutexGuard<'_, SystemInfo> {
+    info.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// 在锁内把一次读取的结果写入 SystemInfo 的函数
+type Publish = Box<dyn FnOnce(&mut SystemInfo) + Send>;
+
+/// 一个需要刷新的采集器在采样线程中的部分，类型擦除后放进同一个列表
+pub trait Task: Send {
+    /// 采集器的名称（Collector::name）
+    fn name(&self) -> &'static str;
+
+    fn refresh(&self) -> Refresh;
+
+    /// 在锁外读取一次，返回写入结果的函数
+    fn read(&mut self, sys: &System) -> Publish;
+}
+
+/// 读取端和把结果写入 SystemInfo 中对应采集器的函数，由 system_info! 为每个采集器生成
+pub struct ReaderTask<R: Reader> {
+    pub name: &'static str,
+    pub reader: R,
+    pub apply: fn(&mut SystemInfo, R::Reading, Instant),
+}
+
+impl<R: Reader> Task for ReaderTask<R> {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn refresh(&self) -> Refresh {
+        self.reader.refresh()
+    }
+
+    fn read(&mut self, sys: &System) -> Publish {
+        let reading = self.reader.read(sys);
+        let at = Instant::now();
+        let apply = self.apply;
+        Box::new(move |info| apply(info, reading, at))
+    }
+}
+
+/// 界面发给采样线程的指令
+pub enum Control {
+    Interval(Duration),               // 修改采样间隔
+    Collectors(Vec<&'static str>),    // 切换视图后需要刷新的采集器（Collector::name）
+}
+
+/// 后台采样：持有唯一的 sysinfo System 和当前显示的数据需要的采集器的读取端
+pub struct Sampler {
+    sys: System,
+    tasks: Vec<Box<dyn Task>>, // 需要刷新的采集器的读取端
+    refresh: Refresh,          // 这些采集器需要 sysinfo 刷新的内容
+}
+
+impl Sampler {
+    pub fn new() -> Sampler {
+        let mut sys = System::new();
+        sys.refresh_cpu_list(CpuRefreshKind::nothing()); // CPU 列表和型号
+        sys.refresh_memory(); // 内存总量
+        Sampler { sys, tasks: Vec::new(), refresh: Refresh::default() }
+    }
+
+    /// 创建采集器时需要的 System
+    pub fn system(&self) -> &System {
+        &self.sys
+    }
+
+    /// 为 collectors 创建读取端，并先刷新一次 System 作为基线，第一次采样才能算出 CPU 使用率。
+    /// 读取端会复制采集器中的配置，所以在配置完成后、启动采样线程前调用
+    pub fn prime(&mut self, info: &mut SystemInfo, collectors: &[&'static str]) {
+        self.set_collectors(info, collectors);
+        self.refresh.apply(&mut self.sys);
+    }
+
+    /// 只保留 collectors 的读取端，为新加入的采集器创建读取端，并重新汇总刷新需求。
+    /// 新读取端从基线开始，停止采样期间的历史接不上，所以清空这些采集器的历史；调用时持有锁，这里不做 I/O
+    fn set_collectors(&mut self, info: &mut SystemInfo, collectors: &[&'static str]) {
+        self.tasks.retain(|task| collectors.contains(&task.name()));
+        let added: Vec<&str> =
+            collectors.iter().copied().filter(|name| !self.tasks.iter().any(|task| task.name() == *name)).collect();
+        for collector in info.collectors_mut() {
+            if added.contains(&collector.name()) {
+                collector.clear_history();
+            }
+        }
+        self.tasks.extend(info.tasks(&added));
+        self.refresh = self.tasks.iter().fold(Refresh::default(), |refresh, task| refresh.union(task.refresh()));
+    }
+
+    /// 采样一轮。sysinfo 刷新和所有读取都在锁外进行，最后加锁一次写入结果，界面最多等待内存中的更新；
+    /// 某个读取卡住时界面照常响应，只是数据不再更新
+    pub fn sample(&mut self, info: &Mutex<SystemInfo>) {
+        self.refresh.apply(&mut self.sys);
+        let results: Vec<Publish> = self.tasks.iter_mut().map(|task| task.read(&self.sys)).collect();
+        let mut info = lock(info);
+        for publish in results {
+            publish(&mut info);
+        }
+    }
+
+    /// 在后台线程中每隔 interval 采样一轮，每轮结束后发送完成时间。返回发送 Control 的发送端和完成通知的接收端；
+    /// 界面丢弃任意一端后线程退出
+    pub fn spawn(mut self, info: Arc<Mutex<SystemInfo>>, interval: Duration) -> (Sender<Control>, Receiver<Instant>) {
+        let (control_sender, controls) = mpsc::channel();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let mut interval = interval;
+            // prime 刚刷新过 CPU，立即采样时 sysinfo 算出的使用率是 0，会作为第一个点进入历史；
+            // 等到能算出有效值再开始第一轮
+            thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+            loop {
+                let started = Instant::now();
+                self.sample(&info);
+                if sender.send(Instant::now()).is_err() {
+                    return;
+                }
+                // 采样本身的耗时计入间隔，慢的时候不再额外等待；等待期间修改的间隔从本轮开始生效
+                loop {
+                    match controls.recv_timeout(interval.saturating_sub(started.elapsed())) {
+                        Ok(Control::Interval(new_interval)) => interval = new_interval,
+                        Ok(Control::Collectors(collectors)) => self.set_collectors(&mut lock(&info), &collectors),
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+            }
+        });
+        (control_sender, receiver)
+    }
+}
@@ -0,0 +1,158 @@
+// src/ui/connections.rs
+use crate::data::connections::ListeningPort;
+use crate::data::Connections;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
+    Frame,
+};
+
+/// 状态汇总中的颜色：CLOSE_WAIT 通常意味着程序没有关闭连接，TIME_WAIT 过多说明短连接太多
+fn state_style(state: &str) -> Style {
+    match state {
+        "CLOSE_WAIT" => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        "TIME_WAIT" => Style::default().fg(Color::Yellow),
+        "ESTABLISHED" => Style::default().fg(Color::Green),
+        _ => Style::default(),
+    }
+}
+
+/// 把 Unix 时间戳格式化为 "HH:MM:SS" (UTC)
+fn format_time(timestamp: u64) -> String {
+    let seconds = timestamp % 86_400;
+    format!("{:02}:{:02}:{:02}", seconds / 3600, seconds % 3600 / 60, seconds % 60)
+}
+
+/// "PID/进程名"，不知道所属进程时为 "-"
+fn owner(pid: Option<u32>, process: &Option<String>) -> String {
+    match (pid, process) {
+        (Some(pid), Some(process)) => format!("{}/{}", pid, process),
+        _ => "-".to_string(),
+    }
+}
+
+/// 监听端口表的一行
+fn port_cells(port: &ListeningPort) -> Vec<Cell<'static>> {
+    vec![
+        Cell::from(port.protocol.name()),
+        Cell::from(port.address.to_string()),
+        Cell::from(owner(port.pid, &port.process)),
+    ]
+}
+
+/// 套接字面板：默认显示所有套接字，listening 为 true 时显示监听端口和端口变化日志
+pub fn draw_connections(f: &mut Frame, area: Rect, connections: &Connections, listening: bool) {
+    if !connections.available {
+        let paragraph = Paragraph::new("Socket statistics require /proc/net/tcp (Linux only).")
+            .block(Block::default().title("Connections").borders(Borders::ALL));
+        f.render_widget(paragraph, area);
+        return;
+    }
+    if listening {
+        draw_listening(f, area, connections);
+    } else {
+        draw_sockets(f, area, connections);
+    }
+}
+
+/// 上方是各状态的数量汇总，下方是所有 TCP/UDP 套接字
+fn draw_sockets(f: &mut Frame, area: Rect, connections: &Connections) {
+    let block = Block::default().borders(Borders::ALL);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(block.inner(area));
+    let height = chunks[1].height.saturating_sub(1) as usize;
+    let total = connections.sockets.len();
+    let title = if total > height {
+        format!("Connections (showing {} of {} sockets)", height, total)
+    } else {
+        format!("Connections ({} sockets)", total)
+    };
+    f.render_widget(block.title(title), area);
+
+    // 1. 各状态的数量
+    let mut spans = Vec::new();
+    for (state, count) in &connections.states {
+        spans.push(Span::styled(format!("{} {}", state, count), state_style(state)));
+        spans.push(Span::raw("  "));
+    }
+    let summary = Paragraph::new(Line::from(spans)).wrap(Wrap { trim: true });
+    f.render_widget(summary, chunks[0]);
+
+    // 2. 套接字明细，只构造能显示出来的行；CLOSE_WAIT 和 ESTABLISHED 排在前面，放不下时截掉的是其余状态
+    let header = Row::new(["PROTO", "LOCAL", "REMOTE", "STATE", "RECV-Q", "SEND-Q", "PID/PROGRAM"])
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+    let rows: Vec<Row> = connections
+        .sockets
+        .iter()
+        .take(height)
+        .map(|socket| {
+            Row::new(vec![
+                Cell::from(socket.protocol.name()),
+                Cell::from(socket.local.to_string()),
+                Cell::from(socket.remote.to_string()),
+                Cell::from(socket.state).style(state_style(socket.state)),
+                Cell::from(socket.rx_queue.to_string()),
+                Cell::from(socket.tx_queue.to_string()),
+                Cell::from(owner(socket.pid, &socket.process)),
+            ])
+        })
+        .collect();
+    let widths = [
+        Constraint::Length(5),
+        Constraint::Length(21),
+        Constraint::Length(21),
+        Constraint::Length(11),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Fill(1),
+    ];
+    f.render_widget(Table::new(rows, widths).header(header), chunks[1]);
+}
+
+/// 左侧是正在监听的端口，右侧是运行期间端口打开/关闭的日志（最新的在最上面）
+fn draw_listening(f: &mut Frame, area: Rect, connections: &Connections) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let header_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let widths = [Constraint::Length(5), Constraint::Length(24), Constraint::Fill(1)];
+    let rows: Vec<Row> = connections.listening.iter().map(|port| Row::new(port_cells(port))).collect();
+    let title = format!("Listening Ports ({})", connections.listening.len());
+    let table = Table::new(rows, widths)
+        .header(Row::new(["PROTO", "ADDRESS", "PID/PROGRAM"]).style(header_style))
+        .block(Block::default().title(title).borders(Borders::ALL));
+    f.render_widget(table, chunks[0]);
+
+    let rows: Vec<Row> = connections
+        .events
+        .iter()
+        .rev()
+        .map(|event| {
+            let (text, color) = if event.opened { ("OPEN", Color::Green) } else { ("CLOSE", Color::Red) };
+            let mut cells = vec![
+                Cell::from(format_time(event.timestamp)),
+                Cell::from(text).style(Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            ];
+            cells.extend(port_cells(&event.port));
+            Row::new(cells)
+        })
+        .collect();
+    let widths = [
+        Constraint::Length(8),
+        Constraint::Length(5),
+        Constraint::Length(5),
+        Constraint::Length(24),
+        Constraint::Fill(1),
+    ];
+    let title = format!("Port Events ({})", connections.events.len());
+    let table = Table::new(rows, widths)
+        .header(Row::new(["UTC", "EVENT", "PROTO", "ADDRESS", "PID/PROGRAM"]).style(header_style))
+        .block(Block::default().title(title).borders(Borders::ALL));
+    f.render_widget(table, chunks[1]);
+}
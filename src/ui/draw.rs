@@ -1,63 +1,210 @@
 // src/ui/draw.rs
-use std::collections::VecDeque;
-use crate::app::App;
+use std::time::{Duration, Instant};
+use crate::app::{App, SystemInfo};
+use crate::data::{format_bytes, format_duration, Cores, CpuTimes, DiskInfo, DiskIo, HostInfo, Memory, Pressure, Sensors, Summary, VmStat};
+use crate::data::sensors::Sensor;
+use crate::data::summary::SummarySample;
+use crate::data::vmstat::VmStatSample;
+use crate::data::cpu_time::CpuTimeSample;
+use crate::data::memory::MemorySample;
 use crate::data::net::NetInfo;
+use crate::data::series::{seconds_before, TimeSeries};
+use crate::modules::{CpuView, Module};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Cell, Chart, Dataset, GraphType, Paragraph, Row, Sparkline, Table, Wrap},
     Frame,
 };
 
+/// 根据使用率选择颜色：低负载绿色，中等黄色，高负载红色
+fn usage_color(usage: f64) -> Color {
+    if usage >= 80.0 {
+        Color::Red
+    } else if usage >= 50.0 {
+        Color::Yellow
+    } else {
+        Color::Green
+    }
+}
+
+/// 时间轴：从 window 之前到现在，x 为相对现在的秒数（见 TimeSeries::points）。
+/// 刻度标签和轴线要占两行，图表太矮时省略
+fn time_axis<'a>(window: Duration, area: Rect) -> Axis<'a> {
+    let axis = Axis::default().style(Color::Gray).bounds([-window.as_secs_f64(), 0.0]);
+    if area.height < 8 {
+        return axis;
+    }
+    axis.labels([
+        format!("-{}", format_duration(window)),
+        format!("-{}", format_duration(window / 2)),
+        "now".to_string(),
+    ])
+}
+
+/// 迷你图的数据：每列对应 [now - window, now] 中的一段时间，宽度和采样间隔变化时覆盖的时间范围不变。
+/// 一次采样代表它之前的整个采样间隔，所以两次采样之间的空列用后一次采样填充；
+/// 第一次采样之前和缺口（NaN）处留空
+pub fn sparkline_data<T>(
+    series: &TimeSeries<T>,
+    now: Instant,
+    width: usize,
+    value: impl Fn(&T) -> f64,
+) -> Vec<Option<u64>> {
+    let buckets = series.buckets(now, width, value);
+    let first = buckets.iter().position(Option::is_some).unwrap_or(buckets.len());
+    let mut next = None;
+    let mut data: Vec<Option<u64>> = buckets
+        .into_iter()
+        .enumerate()
+        .rev()
+        .map(|(i, bucket)| {
+            if bucket.is_some() {
+                next = bucket;
+            }
+            let value = if i < first { None } else { next };
+            value.filter(|v| v.is_finite()).map(|v| v.round().max(0.0) as u64)
+        })
+        .collect();
+    data.reverse();
+    data
+}
+
+/// 折线图最多需要的点数：每个字符两列盲文点，多出的点画出来也分辨不出
+fn chart_points(area: Rect) -> usize {
+    (area.width as usize * 2).max(2)
+}
+
+/// 每个核心一格，左侧是编号和当前使用率，右侧是该核心的历史迷你图。
+/// 放不下时先去掉迷你图只显示使用率；仍然放不下时隐藏最空闲的核心，最后一格显示隐藏的数量
+fn draw_per_core_grid(f: &mut Frame, area: Rect, title: &str, cores: &Cores) {
+    let block = Block::default().title(title).borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    if cores.number == 0 || inner.width == 0 || inner.height == 0 {
+        return;
+    }
+
+    // 1. 计算网格的行列数：每格至少 18 列宽，行数不超过可用高度时尽量少分列
+    const MIN_CELL_WIDTH: u16 = 18;
+    const COMPACT_CELL_WIDTH: u16 = 11; // 只有标签 "C12  99.9% "
+    let height = inner.height as usize;
+    let now = Instant::now();
+    let latest = |index: usize| cores.cores[index].usages.latest().copied().unwrap_or(0.0);
+    let max_cols = (inner.width / MIN_CELL_WIDTH).max(1) as usize;
+    let compact_cols = (inner.width / COMPACT_CELL_WIDTH).max(1) as usize;
+    let (cols, compact) = if cores.number <= height * max_cols {
+        (cores.number.div_ceil(height).max(1), false)
+    } else {
+        (compact_cols.min(cores.number.div_ceil(height)), true)
+    };
+
+    // 2. 仍然放不下时只保留使用率最高的核心（按编号排列），留出最后一格显示隐藏了多少
+    let mut shown: Vec<usize> = (0..cores.number).collect();
+    let capacity = cols * height;
+    let mut hidden = Vec::new();
+    if shown.len() > capacity {
+        shown.sort_by(|&a, &b| latest(b).total_cmp(&latest(a)).then(a.cmp(&b)));
+        hidden = shown.split_off(capacity.saturating_sub(1));
+        shown.sort_unstable();
+    }
+    let cells = shown.len() + usize::from(!hidden.is_empty());
+    let rows = cells.div_ceil(cols);
+
+    let row_areas = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(1); rows])
+        .split(inner);
+
+    // 3. 逐格绘制
+    for (row, row_area) in row_areas.iter().enumerate() {
+        let cell_areas = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, cols as u32); cols])
+            .split(*row_area);
+
+        for (col, cell) in cell_areas.iter().enumerate() {
+            let Some(&index) = shown.get(row * cols + col) else {
+                if row * cols + col == shown.len() && !hidden.is_empty() {
+                    // 隐藏的都是较空闲的核心，标出其中的最高使用率
+                    let busiest = hidden.iter().map(|&i| latest(i)).fold(0.0_f64, f64::max);
+                    let text = format!("+{} ≤{:.0}%", hidden.len(), busiest);
+                    f.render_widget(Paragraph::new(text).style(Style::default().fg(Color::DarkGray)), *cell);
+                }
+                break;
+            };
+            let core = &cores.cores[index];
+            let usage = latest(index);
+            let color = usage_color(usage);
+
+            let label = format!("C{:<3}{:>5.1}% ", index, usage);
+            let label_width = (label.len() as u16).min(cell.width);
+            let label_area = Rect { width: label_width, ..*cell };
+            f.render_widget(Paragraph::new(label).style(Style::default().fg(color)), label_area);
+            if compact {
+                continue;
+            }
+            let spark_area = Rect {
+                x: cell.x + label_width,
+                width: cell.width.saturating_sub(label_width + 1), // 留一列作为格间距
+                ..*cell
+            };
 
-fn draw_net_chart(
+            let history = sparkline_data(&core.usages, now, spark_area.width as usize, |&u| u);
+            let sparkline = Sparkline::default()
+                .data(&history)
+                .max(100)
+                .style(Style::default().fg(color));
+            f.render_widget(sparkline, spark_area);
+        }
+    }
+}
+
+
+/// 两条速率曲线（下载/上传、读取/写入）的图表，Y 轴随数据自适应；
+/// label 不为空时显示在图表左上角
+fn draw_rate_chart(
     f: &mut Frame,
     area: Rect,
     title: &str,
-    download_data: &VecDeque<f64>,
-    upload_data: &VecDeque<f64>,
+    names: (&str, &str),
+    download_data: &TimeSeries<f64>,
+    upload_data: &TimeSeries<f64>,
+    label: &str,
 ) {
-    // 1. 准备下载数据集（绿色）
-    let download_points: Vec<(f64, f64)> = download_data
-        .iter()
-        .enumerate()
-        .map(|(i, &rate)| (i as f64, rate))
-        .collect();
-    let download_dataset = Dataset::default()
-        .name("Download")
-        .graph_type(GraphType::Line)
-        .style(Color::Green)
-        .marker(ratatui::symbols::Marker::Braille)
-        .data(&download_points);
-
-    // 2. 准备上传数据集（红色）
-    let upload_points: Vec<(f64, f64)> = upload_data
-        .iter()
-        .enumerate()
-        .map(|(i, &rate)| (i as f64, rate))
-        .collect();
-    let upload_dataset = Dataset::default()
-        .name("Upload")
-        .graph_type(GraphType::Line)
-        .style(Color::Red)
-        .marker(ratatui::symbols::Marker::Braille)
-        .data(&upload_points);
+    let now = Instant::now();
+    // 1. 按缺口（计数器重置时记录的 NaN）把两条曲线分成若干段，
+    //    下载为绿色、上传为红色，只有第一段带图例名称
+    let max_points = chart_points(area);
+    let download_segments = split_at_gaps(download_data.points(now, max_points, |&rate| rate));
+    let upload_segments = split_at_gaps(upload_data.points(now, max_points, |&rate| rate));
+    let mut datasets = Vec::new();
+    for (segments, name, color) in [
+        (&download_segments, names.0, Color::Green),
+        (&upload_segments, names.1, Color::Red),
+    ] {
+        for (i, points) in segments.iter().enumerate() {
+            let dataset = Dataset::default()
+                .graph_type(GraphType::Line)
+                .style(color)
+                .marker(ratatui::symbols::Marker::Braille)
+                .data(points);
+            datasets.push(if i == 0 { dataset.name(name) } else { dataset });
+        }
+    }
 
     // 3. 动态计算 Y 轴边界，让图表自适应
-    let max_download = download_data.iter().fold(0.0_f64, |a, &b| a.max(b));
-    let max_upload = upload_data.iter().fold(0.0_f64, |a, &b| a.max(b));
+    let max_download = download_data.values().fold(0.0_f64, |a, &b| a.max(b));
+    let max_upload = upload_data.values().fold(0.0_f64, |a, &b| a.max(b));
     let max_rate = max_download.max(max_upload);
     // 如果没有流量，给一个最小值，避免Y轴从0到0
     let y_max = if max_rate > 0.0 { max_rate * 1.1 } else { 10.0 };
 
-    let chart = Chart::new(vec![download_dataset, upload_dataset])
+    let chart = Chart::new(datasets)
         .block(Block::default().title(title).borders(Borders::ALL))
-        .x_axis(
-            Axis::default()
-                .title("Time (ticks)")
-                .style(Color::Gray)
-                .bounds([0.0, download_data.len() as f64]),
-        )
+        .x_axis(time_axis(download_data.window(), area))
         .y_axis(
             Axis::default()
                 .style(Color::Gray)
@@ -67,38 +214,261 @@ fn draw_net_chart(
     // --- 关键改动：先渲染图表框架 ---
     f.render_widget(chart, area);
 
-    // --- 关键改动：只有在有数据时才渲染标签 ---
-    if let (Some(&latest_down), Some(&latest_up)) = (download_data.back(), upload_data.back()) {
-        // 只有当最新速率不为0时才显示标签，避免一直显示 0.0 KB/s
-        if latest_down > 0.0 || latest_up > 0.0 {
+    // --- 关键改动：只有在有标签时才渲染 ---
+    if !label.is_empty() {
+        let label_style = Style::default()
+            .fg(Color::White)
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::BOLD);
+
+        let text_widget = Paragraph::new(label).style(label_style);
+        let text_area = Rect {
+            x: area.x + 1,
+            y: area.y + 1,
+            width: (label.chars().count() as u16).min(area.width.saturating_sub(2)),
+            height: 1,
+        };
+        f.render_widget(text_widget, text_area);
+    }
+}
+
+/// 把速率曲线的点在 NaN（缺口）处断开；至少返回一段，保证图例可见
+fn split_at_gaps(points: Vec<(f64, f64)>) -> Vec<Vec<(f64, f64)>> {
+    let mut segments = vec![Vec::new()];
+    for (x, rate) in points {
+        if rate.is_nan() {
+            if segments.last().is_some_and(|s| !s.is_empty()) {
+                segments.push(Vec::new());
+            }
+        } else if let Some(segment) = segments.last_mut() {
+            segment.push((x, rate));
+        }
+    }
+    if segments.len() > 1 && segments.last().is_some_and(|s| s.is_empty()) {
+        segments.pop();
+    }
+    segments
+}
+
+/// 网络流量的标签；只有当最新速率不为0时才显示，避免一直显示 0.0 KB/s
+fn net_rate_label(download_data: &TimeSeries<f64>, upload_data: &TimeSeries<f64>) -> String {
+    match (download_data.latest(), upload_data.latest()) {
+        (Some(&latest_down), Some(&latest_up)) if latest_down > 0.0 || latest_up > 0.0 => {
             let down_text = NetInfo::format_rate(latest_down);
             let up_text = NetInfo::format_rate(latest_up);
-            let text = format!("↓ {} | ↑ {}", down_text, up_text);
+            format!("↓ {} | ↑ {}", down_text, up_text)
+        }
+        _ => String::new(),
+    }
+}
 
-            let label_style = Style::default()
-                .fg(Color::White)
-                .bg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD);
+/// 网络：默认显示选中接口的速率图，table 为 true 时列出所有接口
+pub fn draw_net(f: &mut Frame, area: Rect, net: &NetInfo, table: bool) {
+    if table {
+        draw_net_table(f, area, net);
+        return;
+    }
+    let title = format!(
+        "Network Usage ({}) [{}/{}]",
+        net.get_interface_name(),
+        net.interfaces.iter().position(|i| i.name == net.interface_name).map_or(0, |p| p + 1),
+        net.interfaces.len()
+    );
+    let empty = TimeSeries::new(net.history);
+    let (download, upload) = match net.selected() {
+        Some(iface) => (&iface.download_rates, &iface.upload_rates),
+        None => (&empty, &empty),
+    };
+    let label = net_rate_label(download, upload);
+    draw_rate_chart(f, area, &title, ("Download", "Upload"), download, upload, &label);
 
-            let text_widget = Paragraph::new(text).style(label_style);
-            let text_area = Rect {
-                x: area.x + 1,
-                y: area.y + 1,
-                width: 30,
-                height: 1,
-            };
-            f.render_widget(text_widget, text_area);
-        }
+    // 选中接口出现过错误或丢包时，在速率标签下方给出提示
+    if let Some(iface) = net.selected()
+        && (iface.packets.errors() > 0 || iface.packets.drops() > 0)
+        && area.height > 3
+    {
+        let packets = &iface.packets;
+        let rates = &iface.packet_rates;
+        let text = format!(
+            "errs {} ({:.0}/s) | drops {} ({:.0}/s)",
+            packets.errors(),
+            rates.errors,
+            packets.drops(),
+            rates.drops
+        );
+        let style = problem_style(packets.errors() + packets.drops(), rates.errors + rates.drops);
+        let text_area = Rect {
+            x: area.x + 1,
+            y: area.y + 2,
+            width: (text.chars().count() as u16).min(area.width.saturating_sub(2)),
+            height: 1,
+        };
+        f.render_widget(Paragraph::new(text).style(style), text_area);
     }
 }
 
+/// 错误和丢包计数的颜色：本次采样仍在增加时为红色，曾经出现过为黄色
+fn problem_style(total: u64, rate: f64) -> Style {
+    if rate > 0.0 {
+        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+    } else if total > 0 {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    }
+}
+
+/// 所有接口的当前速率、包速率、错误/丢包和累计流量
+fn draw_net_table(f: &mut Frame, area: Rect, net: &NetInfo) {
+    let header = Row::new([
+        "IFACE", "DOWN", "UP", "RX PPS", "TX PPS", "ERRS", "DROPS", "MCAST/S", "RX TOTAL", "TX TOTAL", "HISTORY",
+    ])
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+    // 曲线列的宽度：去掉边框、其他列的最小宽度 (8 + 81) 和 10 个列间距后剩下的部分
+    let history_width = (area.width as usize).saturating_sub(2 + 8 + 81 + 10).max(1);
+    let now = Instant::now();
+    let rows: Vec<Row> = net
+        .interfaces
+        .iter()
+        .map(|iface| {
+            // 用下载和上传速率之和画一条简易的字符曲线，每个字符对应历史窗口中的一段时间
+            const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+            let download = sparkline_data(&iface.download_rates, now, history_width, |&rate| rate);
+            let upload = sparkline_data(&iface.upload_rates, now, history_width, |&rate| rate);
+            let totals: Vec<Option<u64>> = download
+                .into_iter()
+                .zip(upload)
+                .map(|(d, u)| Some(d? + u?))
+                .collect();
+            let max = totals.iter().flatten().copied().max().unwrap_or(0);
+            let history: String = totals
+                .iter()
+                .map(|v| match *v {
+                    None => ' ', // 尚无采样，或计数器重置造成的缺口
+                    Some(v) if max > 0 => LEVELS[(v as f64 / max as f64 * 7.0).round() as usize],
+                    Some(_) => LEVELS[0],
+                })
+                .collect();
+
+            let packets = &iface.packets;
+            let rates = &iface.packet_rates;
+            let row = Row::new(vec![
+                Cell::from(iface.name.clone()),
+                Cell::from(NetInfo::format_rate(iface.download_rate)),
+                Cell::from(NetInfo::format_rate(iface.upload_rate)),
+                Cell::from(format!("{:.0}", rates.rx_packets)),
+                Cell::from(format!("{:.0}", rates.tx_packets)),
+                Cell::from(packets.errors().to_string()).style(problem_style(packets.errors(), rates.errors)),
+                Cell::from(packets.drops().to_string()).style(problem_style(packets.drops(), rates.drops)),
+                Cell::from(format!("{:.0}", rates.multicast)),
+                Cell::from(format_bytes(iface.received)),
+                Cell::from(format_bytes(iface.transmitted)),
+                Cell::from(history).style(Style::default().fg(Color::Green)),
+            ]);
+            if iface.name == net.interface_name {
+                row.style(Style::default().add_modifier(Modifier::REVERSED))
+            } else {
+                row
+            }
+        })
+        .collect();
+    let widths = [
+        Constraint::Min(8),
+        Constraint::Length(11),
+        Constraint::Length(11),
+        Constraint::Length(7),
+        Constraint::Length(7),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Length(7),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Fill(1), // 曲线只使用剩余的宽度
+    ];
+    let title = format!("Network Interfaces ({})", net.interfaces.len());
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::default().title(title).borders(Borders::ALL));
+    f.render_widget(table, area);
+}
+
+/// 磁盘 I/O：上方是选中设备的读写速率图，下方是所有设备的明细表
+pub fn draw_disk_io(f: &mut Frame, area: Rect, disk_io: &DiskIo) {
+    let Some(device) = disk_io.selected_device().filter(|_| disk_io.available) else {
+        let paragraph = Paragraph::new("Disk I/O statistics require /proc/diskstats (Linux only).")
+            .block(Block::default().title("Disk I/O").borders(Borders::ALL));
+        f.render_widget(paragraph, area);
+        return;
+    };
+
+    let table_height = (disk_io.devices.len() as u16 + 3).min(area.height / 2);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(table_height)])
+        .split(area);
+
+    let title = format!("Disk I/O ({})", device.name);
+    let label = format!(
+        "R {} | W {} | await {:.1}ms | util {:.0}%",
+        NetInfo::format_rate(device.read_rate),
+        NetInfo::format_rate(device.write_rate),
+        device.await_ms,
+        device.utilization
+    );
+    draw_rate_chart(
+        f,
+        chunks[0],
+        &title,
+        ("Read", "Write"),
+        &device.read_rates,
+        &device.write_rates,
+        &label,
+    );
+
+    let header = Row::new(["DEVICE", "READ", "WRITE", "R IOPS", "W IOPS", "AWAIT", "UTIL"])
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+    let rows: Vec<Row> = disk_io
+        .devices
+        .iter()
+        .map(|d| {
+            let row = Row::new(vec![
+                Cell::from(d.name.clone()),
+                Cell::from(NetInfo::format_rate(d.read_rate)),
+                Cell::from(NetInfo::format_rate(d.write_rate)),
+                Cell::from(format!("{:.0}", d.read_iops)),
+                Cell::from(format!("{:.0}", d.write_iops)),
+                Cell::from(format!("{:.1}ms", d.await_ms)),
+                Cell::from(format!("{:.0}%", d.utilization)).style(Style::default().fg(usage_color(d.utilization))),
+            ]);
+            if d.name == device.name {
+                row.style(Style::default().add_modifier(Modifier::REVERSED))
+            } else {
+                row
+            }
+        })
+        .collect();
+    let widths = [
+        Constraint::Min(6),
+        Constraint::Length(11),
+        Constraint::Length(11),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Length(8),
+        Constraint::Length(5),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::default().title("Block Devices").borders(Borders::ALL));
+    f.render_widget(table, chunks[1]);
+}
+
 
     // ... draw_chart 函数保持不变 ...
 fn draw_chart(
         f: &mut Frame,
         area: Rect,
         title: &str,
-        data: &VecDeque<f64>,
+        data: &TimeSeries<f64>,
         y_label: &str,
         color: Color,
     ) {
@@ -107,11 +477,7 @@ fn draw_chart(
         }
 
         // 1. 准备图表数据
-        let data_points: Vec<(f64, f64)> = data
-            .iter()
-            .enumerate()
-            .map(|(i, &usage)| (i as f64, usage))
-            .collect();
+        let data_points = data.points(Instant::now(), chart_points(area), |&usage| usage);
 
         // 将 name 设为空，以隐藏默认的图例
         let dataset = Dataset::default()
@@ -123,12 +489,7 @@ fn draw_chart(
 
         let chart = Chart::new(vec![dataset])
             .block(Block::default().title(title).borders(Borders::ALL))
-            .x_axis(
-                Axis::default()
-                    .title("Time (ticks)")
-                    .style(Color::Gray)
-                    .bounds([0.0, data.len() as f64]),
-            )
+            .x_axis(time_axis(data.window(), area))
             .y_axis(
                 Axis::default()
                     .title("") // <--- 关键改动：隐藏Y轴标题，因为我们会在标签里显示
@@ -140,7 +501,7 @@ fn draw_chart(
         f.render_widget(chart, area);
 
         // 3. 在图表内部渲染自定义的、更大的标签
-        if let Some(&latest_value) = data.back() {
+        if let Some(&latest_value) = data.latest() {
             // 将标签文字和当前值组合在一起
             let text = format!("{}: {:.1}%", y_label, latest_value);
             
@@ -164,90 +525,871 @@ fn draw_chart(
             f.render_widget(text_widget, text_area);
         }
     }
-/// 主绘制函数，现在根据 App 的标志动态绘制
-pub fn draw(f: &mut Frame, app: &App) {
-    // 1. 创建主布局：上(图表区) -> 下(提示区)
-    let vertical_chunks = Layout::default()
+/// 把稀疏的采样点按时间线性插值：在 [x_min, 0] 上均匀取 `points` 个 x，只保留落在采样范围内的点，
+/// 用于填充面积图。times 为每个采样的 x（升序），采样间隔不均匀时按真实时间插值
+fn interpolate(times: &[f64], data: &[f64], x_min: f64, points: usize) -> Vec<(f64, f64)> {
+    if data.len() < 2 || points < 2 {
+        return times.iter().copied().zip(data.iter().copied()).collect();
+    }
+    let (first, last) = (times[0], times[times.len() - 1]);
+    (0..points)
+        .map(|p| x_min - x_min * p as f64 / (points - 1) as f64)
+        .filter(|&x| x >= first && x <= last)
+        .map(|x| {
+            // times[i] <= x <= times[i + 1]
+            let i = times.partition_point(|&t| t <= x).clamp(1, times.len() - 1) - 1;
+            let span = times[i + 1] - times[i];
+            let t = if span > 0.0 { (x - times[i]) / span } else { 0.0 };
+            (x, data[i] * (1.0 - t) + data[i + 1] * t)
+        })
+        .collect()
+}
+
+/// 堆叠面积图：`layers` 从下到上排列，`lines` 是不参与堆叠、画在最上面的折线；
+/// 每组数据都从 `history` 中逐个采样取出，横坐标使用这些采样的时间
+fn draw_stacked_chart<T>(
+    f: &mut Frame,
+    area: Rect,
+    title: &str,
+    history: &TimeSeries<T>,
+    layers: &[(&str, Color, Vec<f64>)],
+    lines: &[(&str, Color, Vec<f64>)],
+    y_max: f64,
+) {
+    let now = Instant::now();
+    let times: Vec<f64> = history.iter().map(|(at, _)| seconds_before(now, at)).collect();
+    let len = times.len();
+
+    // 1. 逐层累加，得到每层的上边界
+    let mut cumulative: Vec<Vec<f64>> = Vec::with_capacity(layers.len());
+    for (_, _, data) in layers {
+        let below = cumulative.last();
+        let stacked = (0..len)
+            .map(|i| data.get(i).copied().unwrap_or(0.0) + below.map_or(0.0, |b| b[i]))
+            .collect();
+        cumulative.push(stacked);
+    }
+
+    // 2. 插值成足够密的竖线（每个字符两列盲文点，再加倍以免出现空隙），从最上层往下画，下层覆盖上层
+    let density = (area.width as usize).saturating_sub(2) * 4;
+    let x_min = -history.window().as_secs_f64();
+    let points: Vec<Vec<(f64, f64)>> = cumulative.iter().map(|c| interpolate(&times, c, x_min, density)).collect();
+    let mut datasets: Vec<Dataset> = layers
+        .iter()
+        .zip(points.iter())
+        .rev()
+        .map(|((name, color, _), data)| {
+            Dataset::default()
+                .name(*name)
+                .graph_type(GraphType::Bar)
+                .style(*color)
+                .marker(ratatui::symbols::Marker::Braille)
+                .data(data)
+        })
+        .collect();
+
+    // 3. 叠加折线
+    let line_points: Vec<Vec<(f64, f64)>> = lines
+        .iter()
+        .map(|(_, _, data)| times.iter().copied().zip(data.iter().copied()).collect())
+        .collect();
+    for ((name, color, _), data) in lines.iter().zip(line_points.iter()) {
+        datasets.push(
+            Dataset::default()
+                .name(*name)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(*color).add_modifier(Modifier::BOLD))
+                .marker(ratatui::symbols::Marker::Braille)
+                .data(data),
+        );
+    }
+
+    let chart = Chart::new(datasets)
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .x_axis(time_axis(history.window(), area))
+        .y_axis(
+            Axis::default()
+                .style(Color::Gray)
+                .bounds([0.0, y_max]),
+        );
+    f.render_widget(chart, area);
+}
+
+/// CPU 时间分解：上方为全局堆叠图，下方空间足够时逐核心列出最近一次的占比
+fn draw_cpu_breakdown(f: &mut Frame, area: Rect, cpu_times: &CpuTimes) {
+    if !cpu_times.available {
+        let paragraph = Paragraph::new("CPU time breakdown requires /proc/stat (Linux only).")
+            .block(Block::default().title("CPU Time Breakdown").borders(Borders::ALL));
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    // 每核心一行，最多占据三分之一的高度
+    let table_rows = (cpu_times.per_core.len() as u16).min(area.height / 3);
+    let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Min(0),    // 图表区域
-            Constraint::Length(3), // 提示区
+            Constraint::Min(0),
+            Constraint::Length(if table_rows > 0 { table_rows + 2 } else { 0 }),
         ])
-        .split(f.area());
+        .split(area);
+
+    let history = &cpu_times.total.history;
+    let series = |get: fn(&CpuTimeSample) -> f64| -> Vec<f64> {
+        history.values().map(get).collect()
+    };
+    let layers = [
+        ("user", Color::Green, series(|s| s.user)),
+        ("nice", Color::LightGreen, series(|s| s.nice)),
+        ("system", Color::Blue, series(|s| s.system)),
+        ("irq", Color::Magenta, series(|s| s.irq)),
+        ("softirq", Color::LightMagenta, series(|s| s.softirq)),
+        ("iowait", Color::Yellow, series(|s| s.iowait)),
+        ("steal", Color::Red, series(|s| s.steal)),
+    ];
+    draw_stacked_chart(f, chunks[0], "CPU Time Breakdown (%)", history, &layers, &[], 100.0);
+
+    // 左上角标签显示最近一次的主要分量，分组与下方的每核心行相同：sys 只含内核态，irq 含硬中断和软中断
+    let latest = cpu_times.total.latest();
+    let text = format!(
+        "usr {:.1}% sys {:.1}% irq {:.1}% io {:.1}% st {:.1}%",
+        latest.user + latest.nice,
+        latest.system,
+        latest.irq + latest.softirq,
+        latest.iowait,
+        latest.steal
+    );
+    let label_style = Style::default()
+        .fg(Color::White)
+        .bg(Color::DarkGray)
+        .add_modifier(Modifier::BOLD);
+    let text_area = Rect {
+        x: chunks[0].x + 1,
+        y: chunks[0].y + 1,
+        width: (text.len() as u16).min(chunks[0].width.saturating_sub(2)),
+        height: 1,
+    };
+    f.render_widget(Paragraph::new(text).style(label_style), text_area);
+
+    if table_rows == 0 {
+        return;
+    }
+    // 放不下所有核心时只列出最忙的核心，仍按核心编号排列
+    let mut shown: Vec<usize> = (0..cpu_times.per_core.len()).collect();
+    if shown.len() > table_rows as usize {
+        shown.sort_by(|&a, &b| cpu_times.per_core[a].latest().idle.total_cmp(&cpu_times.per_core[b].latest().idle));
+        shown.truncate(table_rows as usize);
+        shown.sort_unstable();
+    }
+    let lines: Vec<String> = shown
+        .iter()
+        .map(|&i| {
+            let s = cpu_times.per_core[i].latest();
+            format!(
+                "C{:<3} usr {:>5.1}  sys {:>5.1}  irq {:>5.1}  io {:>5.1}  st {:>5.1}  idle {:>5.1}",
+                i,
+                s.user + s.nice,
+                s.system,
+                s.irq + s.softirq,
+                s.iowait,
+                s.steal,
+                s.idle
+            )
+        })
+        .collect();
+    let mut title = String::from("Per-Core Breakdown (%)");
+    if shown.len() < cpu_times.per_core.len() {
+        title.push_str(&format!(" busiest {} of {}", shown.len(), cpu_times.per_core.len()));
+    }
+    let table = Paragraph::new(lines.join("\n")).block(Block::default().title(title).borders(Borders::ALL));
+    f.render_widget(table, chunks[1]);
+}
+
+/// 内存明细：已用、可回收 slab、缓冲和缓存按占总内存的百分比堆叠，交换分区使用率为折线
+fn draw_memory_breakdown(f: &mut Frame, area: Rect, memory: &Memory) {
+    let total = memory.info.total_memory.max(1) as f64;
+    let percent = |get: fn(&MemorySample) -> u64| -> Vec<f64> {
+        memory.samples.values().map(|s| get(s) as f64 / total * 100.0).collect()
+    };
+    let layers = [
+        ("used", Color::Cyan, percent(|s| s.used)),
+        ("slab", Color::Magenta, percent(|s| s.slab)),
+        ("buffers", Color::Blue, percent(|s| s.buffers)),
+        ("cached", Color::Yellow, percent(|s| s.cached)),
+    ];
+    let swap: Vec<f64> = memory
+        .samples
+        .values()
+        .map(|s| if s.swap_total > 0 { s.swap_used as f64 / s.swap_total as f64 * 100.0 } else { 0.0 })
+        .collect();
 
-    let chart_area = vertical_chunks[0];
+    let title = format!("Memory Usage (Total: {:.2} GB)", memory.info.get_total_memory_gb());
+    draw_stacked_chart(f, area, &title, &memory.samples, &layers, &[("swap", Color::Red, swap)], 100.0);
+
+    // 左上角显示最近一次的明细
+    let latest = memory.latest();
+    let text = format!(
+        "used {} free {} avail {} buf {} cache {}\nshared {} dirty {} slab {} swap {}/{}",
+        format_bytes(latest.used),
+        format_bytes(latest.free),
+        format_bytes(latest.available),
+        format_bytes(latest.buffers),
+        format_bytes(latest.cached),
+        format_bytes(latest.shared),
+        format_bytes(latest.dirty),
+        format_bytes(latest.slab),
+        format_bytes(latest.swap_used),
+        format_bytes(latest.swap_total),
+    );
+    let width = text.lines().map(|l| l.len()).max().unwrap_or(0) as u16;
+    let label_style = Style::default()
+        .fg(Color::White)
+        .bg(Color::DarkGray)
+        .add_modifier(Modifier::BOLD);
+    let text_area = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: width.min(area.width.saturating_sub(2)),
+        height: 2.min(area.height.saturating_sub(2)),
+    };
+    f.render_widget(Paragraph::new(text).style(label_style), text_area);
+}
 
-    // 2. 根据布尔标志，决定要绘制哪些模块
-    let mut modules_to_draw = Vec::new();
-    if app.show_cpu {
-        modules_to_draw.push("cpu");
+/// 用字符画出宽度为 width 的使用率条
+fn usage_bar(percent: f64, width: usize) -> String {
+    let filled = ((percent / 100.0) * width as f64).round().clamp(0.0, width as f64) as usize;
+    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+/// 根据温度接近临界值的程度选择颜色
+fn temperature_color(sensor: &Sensor) -> Color {
+    match sensor.ratio() {
+        Some(ratio) if ratio >= 0.9 => Color::Red,
+        Some(ratio) if ratio >= 0.75 => Color::Yellow,
+        Some(_) => Color::Green,
+        None => Color::Gray,
     }
-    if app.show_memory {
-        modules_to_draw.push("memory");
+}
+
+/// 温度显示为 "45.0°C"，没有数据时为 "-"
+fn format_temperature(value: Option<f64>) -> String {
+    value.map_or("-".to_string(), |t| format!("{:.1}°C", t))
+}
+
+/// 在 CPU 面板右上角的边框上显示降频警告和封装温度
+fn draw_cpu_badges(f: &mut Frame, area: Rect, cores: &Cores, sensors: &Sensors) {
+    let mut badges = Vec::new();
+    let throttled = cores.throttled_count();
+    if throttled > 0 {
+        let style = Style::default().fg(Color::White).bg(Color::Red).add_modifier(Modifier::BOLD);
+        badges.push((format!(" THROTTLED {}/{} ", throttled, cores.number), style));
+    }
+    if let Some(package) = sensors.package() {
+        let style = Style::default().fg(temperature_color(package)).add_modifier(Modifier::BOLD);
+        badges.push((format!(" {} ", format_temperature(package.temperature)), style));
     }
-    // 预留位置，未来启用
-    // if app.show_gpu { modules_to_draw.push("gpu"); }
-    if app.show_net { modules_to_draw.push("net"); }
 
-    // 3. 动态生成布局约束
-    // 如果没有选择任何模块（理论上不应该发生），则默认显示CPU
-    if modules_to_draw.is_empty() {
-        modules_to_draw.push("cpu");
+    // 从右往左排列，放不下时就不再显示
+    let mut right = area.right().saturating_sub(2);
+    for (text, style) in badges.into_iter().rev() {
+        let width = text.chars().count() as u16;
+        if right < area.x + width + 2 {
+            break;
+        }
+        right -= width;
+        let text_area = Rect { x: right, y: area.y, width, height: 1 };
+        f.render_widget(Paragraph::new(text).style(style), text_area);
+        right = right.saturating_sub(1);
     }
-    
-    let constraints: Vec<Constraint> = modules_to_draw
+}
+
+/// 每个核心的频率历史；降频的核心显示为红色，灰色虚线为 cpufreq 中的最高频率
+fn draw_cpu_frequency(f: &mut Frame, area: Rect, cores: &Cores) {
+    let now = Instant::now();
+    let window = cores.usages.window();
+    let max_points = chart_points(area);
+    let series: Vec<Vec<(f64, f64)>> =
+        cores.cores.iter().map(|c| c.frequencies.points(now, max_points, |&mhz| mhz)).collect();
+    let max_frequency = cores.cores.iter().filter_map(|c| c.max_frequency).max();
+    let max_line: Vec<(f64, f64)> = match max_frequency {
+        Some(max) => vec![(-window.as_secs_f64(), max as f64), (0.0, max as f64)],
+        None => Vec::new(),
+    };
+
+    let mut datasets: Vec<Dataset> = cores
+        .cores
         .iter()
-        .map(|_| Constraint::Percentage(100 / modules_to_draw.len() as u16))
+        .zip(series.iter())
+        .map(|(core, points)| {
+            Dataset::default()
+                .graph_type(GraphType::Line)
+                .style(if core.throttled { Color::Red } else { Color::Cyan })
+                .marker(ratatui::symbols::Marker::Braille)
+                .data(points)
+        })
         .collect();
+    datasets.push(
+        Dataset::default()
+            .graph_type(GraphType::Line)
+            .style(Color::DarkGray)
+            .marker(ratatui::symbols::Marker::Dot)
+            .data(&max_line),
+    );
 
+    let observed = cores
+        .cores
+        .iter()
+        .flat_map(|c| c.frequencies.values())
+        .fold(0.0_f64, |a, &b| a.max(b));
+    let y_max = observed.max(max_frequency.unwrap_or(0) as f64);
+    let y_max = if y_max > 0.0 { y_max * 1.1 } else { 1000.0 };
+
+    let average = if cores.cores.is_empty() {
+        0
+    } else {
+        cores.cores.iter().map(|c| c.frequency).sum::<u64>() / cores.cores.len() as u64
+    };
+    let mut label = format!("avg {} MHz", average);
+    if let Some(max) = max_frequency {
+        label.push_str(&format!(" / max {} MHz", max));
+    }
+    label.push_str(&format!(" | governor: {}", cores.governor().unwrap_or("n/a")));
+
+    let chart = Chart::new(datasets)
+        .block(Block::default().title("CPU Frequency").borders(Borders::ALL))
+        .x_axis(time_axis(window, area))
+        .y_axis(Axis::default().style(Color::Gray).bounds([0.0, y_max]));
+    f.render_widget(chart, area);
+
+    let label_style = Style::default()
+        .fg(Color::White)
+        .bg(Color::DarkGray)
+        .add_modifier(Modifier::BOLD);
+    let text_area = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: (label.chars().count() as u16).min(area.width.saturating_sub(2)),
+        height: 1,
+    };
+    f.render_widget(Paragraph::new(label).style(label_style), text_area);
+}
+
+/// 温度传感器：上方是所有传感器的温度历史，下方是当前、最高和临界温度
+pub fn draw_sensors(f: &mut Frame, area: Rect, sensors: &Sensors) {
+    if sensors.sensors.is_empty() {
+        let paragraph = Paragraph::new("No temperature sensors found.")
+            .block(Block::default().title("Sensors").borders(Borders::ALL));
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let table_height = (sensors.sensors.len() as u16 + 3).min(area.height / 2);
     let chunks = Layout::default()
-        .direction(Direction::Horizontal) // 水平排列
-        .constraints(constraints)
-        .split(chart_area);
-
-    // 4. 按顺序绘制选中的模块
-    for (i, module) in modules_to_draw.iter().enumerate() {
-        match *module {
-            "cpu" => {
-                draw_chart(
-                    f,
-                    chunks[i],
-                    "CPU Usage History",
-                    &app.system_info.cores.usages,
-                    "Usage ",
-                    Color::Yellow,
-                );
-            }
-            "memory" => {
-                let total_mem_gb = app.system_info.memory.info.get_total_memory_gb();
-                let memory_title = format!("Memory Usage (Total: {:.2} GB)", total_mem_gb);
-                draw_chart(
-                    f,
-                    chunks[i],
-                    &memory_title,
-                    &app.system_info.memory.usages,
-                    "Usage ",
-                    Color::Cyan,
-                );
-            }
-            // 预留未来模块的绘制逻辑
-            // "gpu" => { /* ... 调用 draw_chart ... */ }
-            "net" => {
-                let title = format!("Network Usage ({})", app.system_info.net.get_interface_name());
-                draw_net_chart(
-                    f,
-                    chunks[i],
-                    &title,
-                    &app.system_info.net.download_rates,
-                    &app.system_info.net.upload_rates,
-                );
-            }
-            _ => {} // 忽略未知模块
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(table_height)])
+        .split(area);
+
+    // 1. 温度历史，每个传感器一条曲线
+    const PALETTE: [Color; 6] = [Color::Cyan, Color::Magenta, Color::Yellow, Color::Green, Color::Blue, Color::Red];
+    let now = Instant::now();
+    let max_points = chart_points(chunks[0]);
+    let series: Vec<Vec<(f64, f64)>> =
+        sensors.sensors.iter().map(|s| s.history.points(now, max_points, |&t| t)).collect();
+    let datasets: Vec<Dataset> = sensors
+        .sensors
+        .iter()
+        .zip(series.iter())
+        .enumerate()
+        .map(|(i, (sensor, points))| {
+            Dataset::default()
+                .name(sensor.label.clone())
+                .graph_type(GraphType::Line)
+                .style(PALETTE[i % PALETTE.len()])
+                .marker(ratatui::symbols::Marker::Braille)
+                .data(points)
+        })
+        .collect();
+    let max_temperature = sensors
+        .sensors
+        .iter()
+        .flat_map(|s| s.history.values())
+        .fold(0.0_f64, |a, &b| a.max(b));
+    let chart = Chart::new(datasets)
+        .block(Block::default().title("Temperature History").borders(Borders::ALL))
+        .x_axis(time_axis(sensors.history, chunks[0]))
+        .y_axis(
+            Axis::default()
+                .style(Color::Gray)
+                .bounds([0.0, max_temperature.max(50.0) * 1.1]),
+        );
+    f.render_widget(chart, chunks[0]);
+
+    // 2. 当前值明细，接近临界温度时变色
+    let header = Row::new(["SENSOR", "TEMP", "MAX", "CRITICAL"])
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+    let rows: Vec<Row> = sensors
+        .sensors
+        .iter()
+        .enumerate()
+        .map(|(i, sensor)| {
+            Row::new(vec![
+                // 标签使用与曲线相同的颜色，图例放不下时也能对应
+                Cell::from(sensor.label.clone()).style(PALETTE[i % PALETTE.len()]),
+                Cell::from(format_temperature(sensor.temperature))
+                    .style(Style::default().fg(temperature_color(sensor)).add_modifier(Modifier::BOLD)),
+                Cell::from(format_temperature(sensor.max)),
+                Cell::from(format_temperature(sensor.critical)),
+            ])
+        })
+        .collect();
+    let widths = [Constraint::Fill(1), Constraint::Length(9), Constraint::Length(9), Constraint::Length(9)];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::default().title(format!("Sensors ({})", sensors.sensors.len())).borders(Borders::ALL));
+    f.render_widget(table, chunks[1]);
+}
+
+/// 停顿比例的颜色：超过 10% 已能感觉到卡顿，超过 25% 资源严重不足
+fn pressure_color(stall: f64) -> Color {
+    if stall >= 25.0 {
+        Color::Red
+    } else if stall >= 10.0 {
+        Color::Yellow
+    } else {
+        Color::Green
+    }
+}
+
+/// PSI：上方是 cpu / memory / io 的 some avg10 历史，下方是各资源的 avg10 / avg60 / avg300
+pub fn draw_pressure(f: &mut Frame, area: Rect, pressure: &Pressure) {
+    if !pressure.available {
+        let paragraph = Paragraph::new(
+            "Pressure stall information is not available: requires Linux 4.20+ with CONFIG_PSI (and not booted with psi=0).",
+        )
+        .wrap(Wrap { trim: true })
+        .block(Block::default().title("Pressure (PSI)").borders(Borders::ALL));
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(pressure.resources.len() as u16 + 3)])
+        .split(area);
+
+    // 1. some avg10 的历史曲线
+    const COLORS: [Color; 3] = [Color::Yellow, Color::Magenta, Color::Cyan];
+    let now = Instant::now();
+    let max_points = chart_points(chunks[0]);
+    let series: Vec<Vec<(f64, f64)>> =
+        pressure.resources.iter().map(|r| r.history.points(now, max_points, |&v| v)).collect();
+    let datasets: Vec<Dataset> = pressure
+        .resources
+        .iter()
+        .zip(series.iter())
+        .enumerate()
+        .map(|(i, (resource, points))| {
+            Dataset::default()
+                .name(resource.name)
+                .graph_type(GraphType::Line)
+                .style(COLORS[i % COLORS.len()])
+                .marker(ratatui::symbols::Marker::Braille)
+                .data(points)
+        })
+        .collect();
+    let max = pressure
+        .resources
+        .iter()
+        .flat_map(|r| r.history.values())
+        .fold(0.0_f64, |a, &b| a.max(b));
+    let window = pressure.resources.first().map_or(Duration::ZERO, |r| r.history.window());
+    let chart = Chart::new(datasets)
+        .block(Block::default().title("Pressure (PSI some avg10 %)").borders(Borders::ALL))
+        .x_axis(time_axis(window, chunks[0]))
+        // 停顿比例通常很小，至少显示到 10%
+        .y_axis(Axis::default().style(Color::Gray).bounds([0.0, (max * 1.1).max(10.0)]));
+    f.render_widget(chart, chunks[0]);
+
+    // 2. 当前值，some avg10 按停顿比例着色
+    let header = Row::new(["RESOURCE", "SOME 10s", "60s", "300s", "FULL 10s", "60s", "300s"])
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+    let rows: Vec<Row> = pressure
+        .resources
+        .iter()
+        .enumerate()
+        .map(|(i, r)| {
+            let full = |value: Option<f64>| value.map_or("-".to_string(), |v| format!("{:.2}", v));
+            Row::new(vec![
+                Cell::from(r.name).style(COLORS[i % COLORS.len()]),
+                Cell::from(format!("{:.2}", r.some.avg10)).style(Style::default().fg(pressure_color(r.some.avg10))),
+                Cell::from(format!("{:.2}", r.some.avg60)),
+                Cell::from(format!("{:.2}", r.some.avg300)),
+                Cell::from(full(r.full.map(|l| l.avg10))),
+                Cell::from(full(r.full.map(|l| l.avg60))),
+                Cell::from(full(r.full.map(|l| l.avg300))),
+            ])
+        })
+        .collect();
+    let widths = [
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Length(8),
+        Constraint::Length(6),
+        Constraint::Length(6),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::default().title("Pressure Stall").borders(Borders::ALL));
+    f.render_widget(table, chunks[1]);
+}
+
+/// 平均负载相对核心数的颜色：低于 70% 绿色，低于 100% 黄色，超过核心数红色
+fn load_color(load: f64, cores: usize) -> Color {
+    let ratio = load / cores.max(1) as f64;
+    if ratio >= 1.0 {
+        Color::Red
+    } else if ratio >= 0.7 {
+        Color::Yellow
+    } else {
+        Color::Green
+    }
+}
+
+/// 指标行：(标签和当前值, 颜色, 按迷你图宽度取出的历史（见 sparkline_data）, 迷你图满刻度；None 表示按历史最大值)
+pub type MetricRow<'a> = (String, Color, Box<dyn Fn(usize) -> Vec<Option<u64>> + 'a>, Option<u64>);
+
+/// 每项指标一行，左边是当前值，右边是历史迷你图
+pub fn draw_metric_rows(f: &mut Frame, area: Rect, metrics: Vec<MetricRow>) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(1); metrics.len()])
+        .split(area);
+    for ((label, color, data, max), row) in metrics.into_iter().zip(rows.iter()) {
+        let label_width = (label.chars().count() as u16 + 1).min(row.width);
+        let label_area = Rect { width: label_width, ..*row };
+        let spark_area = Rect { x: row.x + label_width, width: row.width.saturating_sub(label_width), ..*row };
+        f.render_widget(Paragraph::new(label).style(Style::default().fg(color)), label_area);
+
+        let data = data(spark_area.width as usize);
+        let mut sparkline = Sparkline::default().data(&data).style(Style::default().fg(color));
+        if let Some(max) = max {
+            sparkline = sparkline.max(max);
+        }
+        f.render_widget(sparkline, spark_area);
+    }
+}
+
+/// 内存面板下方的分页、交换、缺页和 OOM kill 速率
+fn draw_vmstat(f: &mut Frame, area: Rect, vmstat: &VmStat) {
+    let block = Block::default().title("Paging / Swapping").borders(Borders::ALL);
+    if !vmstat.available {
+        let paragraph = Paragraph::new("Paging statistics require /proc/vmstat (Linux only).").block(block);
+        f.render_widget(paragraph, area);
+        return;
+    }
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let latest = vmstat.latest();
+    let now = Instant::now();
+    let history = |value: fn(&VmStatSample) -> f64| -> Box<dyn Fn(usize) -> Vec<Option<u64>> + '_> {
+        Box::new(move |width| sparkline_data(&vmstat.samples, now, width, value))
+    };
+    // 任何换入换出都值得注意，持续每秒上百页就是交换风暴
+    let swap_color = |rate: f64| {
+        if rate >= 100.0 {
+            Color::Red
+        } else if rate > 0.0 {
+            Color::Yellow
+        } else {
+            Color::Green
+        }
+    };
+    let major_color = if latest.major_faults >= 1000.0 {
+        Color::Red
+    } else if latest.major_faults >= 100.0 {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+    let oom_color = if latest.oom_kills > 0.0 {
+        Color::Red
+    } else if vmstat.oom_kills > 0 {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+    let per_second = |kb: f64| format!("{}/s", format_bytes((kb * 1024.0) as u64));
+
+    let metrics: Vec<MetricRow> = vec![
+        (format!("{:<13}{:>9.0} pg/s", "Swap in", latest.swap_in), swap_color(latest.swap_in), history(|s| s.swap_in), None),
+        (format!("{:<13}{:>9.0} pg/s", "Swap out", latest.swap_out), swap_color(latest.swap_out), history(|s| s.swap_out), None),
+        (format!("{:<13}{:>14}", "Page in", per_second(latest.page_in)), Color::Cyan, history(|s| s.page_in), None),
+        (format!("{:<13}{:>14}", "Page out", per_second(latest.page_out)), Color::Cyan, history(|s| s.page_out), None),
+        (format!("{:<13}{:>12.0}/s", "Major faults", latest.major_faults), major_color, history(|s| s.major_faults), None),
+        (format!("{:<13}{:>12.0}/s", "Minor faults", latest.minor_faults), Color::Cyan, history(|s| s.minor_faults), None),
+        (format!("{:<13}{:>8} total", "OOM kills", vmstat.oom_kills), oom_color, history(|s| s.oom_kills), None),
+    ];
+    draw_metric_rows(f, inner, metrics);
+}
+
+/// 系统概况：每项指标一行，左边是当前值，右边是历史迷你图
+pub fn draw_summary(f: &mut Frame, area: Rect, summary: &Summary, cores: &Cores) {
+    let block = Block::default()
+        .title(format!("System Summary ({} cores)", cores.number))
+        .borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let latest = summary.latest();
+    let now = Instant::now();
+    let history = |value: fn(&SummarySample) -> f64| -> Box<dyn Fn(usize) -> Vec<Option<u64>> + '_> {
+        Box::new(move |width| sparkline_data(&summary.samples, now, width, value))
+    };
+
+    // 负载曲线以核心数为满刻度，超过核心数时按实际最大值
+    let max_load = summary
+        .samples
+        .values()
+        .flat_map(|s| s.load)
+        .fold(cores.number as f64, f64::max);
+    let mut metrics: Vec<MetricRow> = Vec::new();
+    for (i, name) in ["Load 1m", "Load 5m", "Load 15m"].iter().enumerate() {
+        let load = latest.load[i];
+        metrics.push((
+            format!("{:<10}{:>6.2} ({:>3.0}%)", name, load, load / cores.number.max(1) as f64 * 100.0),
+            load_color(load, cores.number),
+            Box::new(move |width| sparkline_data(&summary.samples, now, width, |s| s.load[i] * 100.0)),
+            Some((max_load * 100.0).ceil() as u64),
+        ));
+    }
+    if summary.run_queue_available {
+        let running_color = if latest.procs_running as usize > cores.number { Color::Yellow } else { Color::Green };
+        let blocked_color = if latest.procs_blocked > 0 { Color::Yellow } else { Color::Green };
+        metrics.push((format!("{:<10}{:>6}", "Running", latest.procs_running), running_color, history(|s| s.procs_running as f64), None));
+        metrics.push((format!("{:<10}{:>6}", "Blocked", latest.procs_blocked), blocked_color, history(|s| s.procs_blocked as f64), None));
+    }
+    metrics.push((format!("{:<10}{:>6}", "Processes", latest.processes), Color::Cyan, history(|s| s.processes as f64), None));
+    metrics.push((format!("{:<10}{:>6}", "Threads", latest.threads), Color::Cyan, history(|s| s.threads as f64), None));
+    let zombie_color = if latest.zombies > 0 { Color::Red } else { Color::Green };
+    metrics.push((format!("{:<10}{:>6}", "Zombies", latest.zombies), zombie_color, history(|s| s.zombies as f64), None));
+
+    draw_metric_rows(f, inner, metrics);
+}
+
+/// 磁盘空间表：每个挂载点一行，带空间使用率条和 inode 使用率
+pub fn draw_disk_table(f: &mut Frame, area: Rect, disk: &DiskInfo) {
+    const BAR_WIDTH: usize = 10;
+    let header = Row::new(["MOUNT", "FS", "SIZE", "USED", "AVAIL", "USE%", "", "INODE%"])
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+
+    let rows: Vec<Row> = disk
+        .entries
+        .iter()
+        .map(|entry| {
+            let percent = entry.usage_percent();
+            let inode_cell = match entry.inode_percent() {
+                Some(p) => Cell::from(format!("{:.0}%", p)).style(Style::default().fg(usage_color(p))),
+                None => Cell::from("-"),
+            };
+            Row::new(vec![
+                Cell::from(entry.mount_point.clone()),
+                Cell::from(entry.fs_type.clone()),
+                Cell::from(format_bytes(entry.total)),
+                Cell::from(format_bytes(entry.used)),
+                Cell::from(format_bytes(entry.available)),
+                // 和 df 一样向上取整，快满时不会显示成 100% 以下的假象
+                Cell::from(format!("{:.0}%", percent.ceil())),
+                Cell::from(usage_bar(percent, BAR_WIDTH)).style(Style::default().fg(usage_color(percent))),
+                inode_cell,
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Min(8),
+        Constraint::Length(6),
+        Constraint::Length(7),
+        Constraint::Length(7),
+        Constraint::Length(7),
+        Constraint::Length(4),
+        Constraint::Length(BAR_WIDTH as u16),
+        Constraint::Length(6),
+    ];
+    let title = format!("Disk Usage ({} filesystems)", disk.entries.len());
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::default().title(title).borders(Borders::ALL));
+    f.render_widget(table, area);
+}
+
+/// 把区域划分成网格：不超过三个模块时排成一行，否则每行 ceil(sqrt(n)) 个
+fn grid_layout(area: Rect, count: usize) -> Vec<Rect> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let cols = if count <= 3 { count } else { (count as f64).sqrt().ceil() as usize };
+    let rows = count.div_ceil(cols);
+
+    let row_areas = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Ratio(1, rows as u32); rows])
+        .split(area);
+
+    let mut cells = Vec::with_capacity(count);
+    for (row, row_area) in row_areas.iter().enumerate() {
+        // 最后一行的模块可能较少，平均分配这一行的宽度
+        let in_row = (count - row * cols).min(cols);
+        let cell_areas = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, in_row as u32); in_row])
+            .split(*row_area);
+        cells.extend(cell_areas.iter().copied());
+    }
+    cells
+}
+
+/// CPU 模块：按当前视图绘制，右上角显示降频和封装温度
+pub fn draw_cpu_panel(f: &mut Frame, area: Rect, view: CpuView, info: &SystemInfo) {
+    match view {
+        CpuView::Average => {
+            draw_chart(
+                f,
+                area,
+                "CPU Usage History",
+                &info.cores.usages,
+                "Usage ",
+                Color::Yellow,
+            );
+        }
+        CpuView::PerCore => {
+            let title = format!("Per-Core CPU Usage ({} cores)", info.cores.number);
+            draw_per_core_grid(f, area, &title, &info.cores);
+        }
+        CpuView::Breakdown => {
+            draw_cpu_breakdown(f, area, &info.cpu_times);
+        }
+        CpuView::Frequency => {
+            draw_cpu_frequency(f, area, &info.cores);
         }
     }
+    draw_cpu_badges(f, area, &info.cores, &info.sensors);
+}
 
-    // 5. 渲染底部的提示文字
-    let paragraph = Paragraph::new("Press 'q' to quit.")
+/// 内存模块：打开分页/交换面板时放在内存图表下方
+pub fn draw_memory_panel(f: &mut Frame, area: Rect, show_vmstat: bool, info: &SystemInfo) {
+    if show_vmstat {
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(9)])
+            .split(area);
+        draw_memory_breakdown(f, split[0], &info.memory);
+        draw_vmstat(f, split[1], &info.vmstat);
+    } else {
+        draw_memory_breakdown(f, area, &info.memory);
+    }
+}
+
+/// 顶部的主机信息栏：主机名、系统和内核版本、运行时间、CPU 型号、核心数和内存总量
+fn draw_header(f: &mut Frame, area: Rect, app: &App, info: &SystemInfo) {
+    let host = &info.host;
+    let separator = || Span::styled(" │ ", Style::default().fg(Color::DarkGray));
+
+    let mut spans = vec![
+        Span::styled(
+            format!(" {} ", host.hostname),
+            Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::raw(format!("{} (kernel {})", host.os, host.kernel)),
+        separator(),
+        Span::raw(format!("up {}", HostInfo::format_uptime(host.uptime()))),
+    ];
+    if let Some(cpu) = info.cores.cores.first() {
+        spans.push(separator());
+        spans.push(Span::raw(format!("{} ({}) × {}", cpu.brand.trim(), cpu.vendor_id, info.cores.number)));
+    }
+    spans.push(separator());
+    spans.push(Span::raw(format!("RAM {}", format_bytes(info.memory.info.total_memory))));
+    spans.push(separator());
+    spans.push(Span::styled(
+        format!("every {} · {}", format_duration(app.interval), format_duration(app.history)),
+        Style::default().fg(Color::DarkGray),
+    ));
+    // 采样线程卡住（例如读取挂死的 NFS）时界面照常响应，但要提示数据已经过时
+    if let Some(age) = app.stale_for() {
+        spans.push(separator());
+        spans.push(Span::styled(
+            format!("stale {}s", age.as_secs()),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// 主绘制函数，根据启用的模块动态绘制
+pub fn draw(f: &mut Frame, app: &App, info: &SystemInfo) {
+    // 1. 创建主布局：上(主机信息) -> 中(图表区) -> 下(提示区)
+    let vertical_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // 主机信息栏
+            Constraint::Min(0),    // 图表区域
+            Constraint::Length(3), // 提示区
+        ])
+        .split(f.area());
+
+    draw_header(f, vertical_chunks[0], app, info);
+    let mut chart_area = vertical_chunks[1];
+
+    // 2. 按注册表的顺序取出要绘制的模块；进程表等模块占据图表下方的区域
+    let (bottom, grid): (Vec<&Module>, Vec<&Module>) = app.enabled_modules().partition(|module| module.bottom);
+    if !bottom.is_empty() {
+        // 只选择了这些模块时占满整个区域
+        let bottom_area = if grid.is_empty() {
+            chart_area
+        } else {
+            let split = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(chart_area);
+            chart_area = split[0];
+            split[1]
+        };
+        let chunks = grid_layout(bottom_area, bottom.len());
+        for (module, area) in bottom.iter().zip(chunks) {
+            (module.draw)(f, area, app, info);
+        }
+    }
+
+    // 3. 其余模块按网格排列
+    let chunks = grid_layout(chart_area, grid.len());
+    for (module, area) in grid.iter().zip(chunks) {
+        (module.draw)(f, area, app, info);
+    }
+
+    // 5. 渲染底部的提示文字；正在输入的模块只显示它自己的说明
+    let help = if let Some(message) = &app.status_message {
+        message.clone()
+    } else if let Some(module) = app.enabled_modules().find(|module| (module.modal)(app)) {
+        (module.keys)(app)
+    } else {
+        let mut help = String::from("Press 'q' to quit, '[' / ']' interval, '{' / '}' history.");
+        for keys in app.enabled_modules().map(|module| (module.keys)(app)).filter(|keys| !keys.is_empty()) {
+            help.push(' ');
+            help.push_str(&keys);
+        }
+        help
+    };
+    let paragraph = Paragraph::new(help)
         .block(Block::default().borders(Borders::ALL));
-    f.render_widget(paragraph, vertical_chunks[1]);
+    f.render_widget(paragraph, vertical_chunks[2]);
+
+    // 弹出窗口最后绘制，覆盖在其它内容之上
+    for module in app.enabled_modules() {
+        (module.overlay)(f, app, info);
+    }
 }
\ No newline at end of file
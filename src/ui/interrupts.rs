@@ -0,0 +1,124 @@
+// src/ui/interrupts.rs
+use crate::data::interrupts::{InterruptSample, Irq};
+use crate::data::Interrupts;
+use crate::ui::draw::{draw_metric_rows, sparkline_data, MetricRow};
+use std::time::Instant;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+/// 把每秒次数格式化为 "950"、"12.3K"、"1.2M"
+fn format_rate(rate: f64) -> String {
+    if rate >= 1_000_000.0 {
+        format!("{:.1}M", rate / 1_000_000.0)
+    } else if rate >= 1000.0 {
+        format!("{:.1}K", rate / 1000.0)
+    } else {
+        format!("{:.0}", rate)
+    }
+}
+
+/// 每个 CPU 一格；某个 CPU 承担了绝大部分中断时标黄，这通常说明 IRQ 亲和性没有分散开
+fn cpu_cells(per_cpu: &[f64], rate: f64, visible: usize) -> Vec<Cell<'static>> {
+    let concentrated = per_cpu.len() > 1 && rate >= 100.0;
+    per_cpu
+        .iter()
+        .take(visible)
+        .map(|&value| {
+            let style = if concentrated && value >= rate * 0.9 {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else if value > 0.0 {
+                Style::default()
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            Cell::from(format_rate(value)).style(style)
+        })
+        .collect()
+}
+
+fn irq_row(irq: &Irq, visible: usize) -> Row<'static> {
+    let name_style = if irq.softirq {
+        Style::default().fg(Color::Magenta)
+    } else {
+        Style::default().fg(Color::Cyan)
+    };
+    let mut cells = vec![Cell::from(irq.name.clone()).style(name_style), Cell::from(format_rate(irq.rate))];
+    cells.extend(cpu_cells(&irq.per_cpu, irq.rate, visible));
+    let device = if irq.softirq { format!("softirq {}", irq.device) } else { irq.device.clone() };
+    cells.push(Cell::from(device.trim_end().to_string()));
+    Row::new(cells)
+}
+
+/// 上方是上下文切换、硬中断、软中断的速率，下方是按速率排序的每个 IRQ 在各 CPU 上的分布
+pub fn draw_interrupts(f: &mut Frame, area: Rect, interrupts: &Interrupts) {
+    let block = Block::default()
+        .title(format!("Interrupts / Context Switches ({} CPUs)", interrupts.cpus))
+        .borders(Borders::ALL);
+    if !interrupts.available {
+        let paragraph = Paragraph::new("Interrupt statistics require /proc/stat and /proc/interrupts (Linux only).").block(block);
+        f.render_widget(paragraph, area);
+        return;
+    }
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(inner);
+
+    let latest = interrupts.latest();
+    let now = Instant::now();
+    let history = |value: fn(&InterruptSample) -> f64| -> Box<dyn Fn(usize) -> Vec<Option<u64>> + '_> {
+        Box::new(move |width| sparkline_data(&interrupts.samples, now, width, value))
+    };
+    let metrics: Vec<MetricRow> = vec![
+        (format!("{:<17}{:>8}/s", "Context switches", format_rate(latest.context_switches)), Color::Green, history(|s| s.context_switches), None),
+        (format!("{:<17}{:>8}/s", "Interrupts", format_rate(latest.interrupts)), Color::Cyan, history(|s| s.interrupts), None),
+        (format!("{:<17}{:>8}/s", "Softirqs", format_rate(latest.softirqs)), Color::Magenta, history(|s| s.softirqs), None),
+    ];
+    draw_metric_rows(f, chunks[0], metrics);
+
+    // 设备名至少留 16 列，其余宽度尽量多放 CPU 列
+    const NAME: u16 = 8;
+    const RATE: u16 = 8;
+    const CPU: u16 = 7;
+    let available = chunks[1].width.saturating_sub(NAME + RATE + 16 + 3);
+    let visible = ((available / (CPU + 1)) as usize).min(interrupts.cpus);
+
+    let mut header = vec![Cell::from("IRQ"), Cell::from("RATE/S")];
+    header.extend((0..visible).map(|cpu| Cell::from(format!("CPU{}", cpu))));
+    header.push(Cell::from(if visible < interrupts.cpus {
+        format!("DEVICE (+{} CPUs)", interrupts.cpus - visible)
+    } else {
+        "DEVICE".to_string()
+    }));
+    let header = Row::new(header).style(Style::default().add_modifier(Modifier::BOLD));
+
+    // 第一行是每个 CPU 的合计，负载明显高于平均值的 CPU 标黄
+    let totals = interrupts.per_cpu_totals();
+    let total: f64 = totals.iter().sum();
+    let mean = total / totals.len().max(1) as f64;
+    let mut total_cells = vec![Cell::from("ALL"), Cell::from(format_rate(total))];
+    total_cells.extend(totals.iter().take(visible).map(|&value| {
+        let style = if totals.len() > 1 && value > mean * 2.0 && value >= 100.0 {
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        Cell::from(format_rate(value)).style(style)
+    }));
+    total_cells.push(Cell::from("all hard and soft interrupts"));
+    let mut rows = vec![Row::new(total_cells).style(Style::default().add_modifier(Modifier::BOLD))];
+    rows.extend(interrupts.irqs.iter().map(|irq| irq_row(irq, visible)));
+
+    let mut widths = vec![Constraint::Length(NAME), Constraint::Length(RATE)];
+    widths.extend(std::iter::repeat_n(Constraint::Length(CPU), visible));
+    widths.push(Constraint::Fill(1));
+    let table = Table::new(rows, widths).header(header);
+    f.render_widget(table, chunks[1]);
+}
@@ -1 +1,4 @@
-pub mod draw;
\ No newline at end of file
+pub mod connections;
+pub mod draw;
+pub mod interrupts;
+pub mod process;
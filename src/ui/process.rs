@@ -0,0 +1,362 @@
+// src/ui/process.rs
+use crate::app::App;
+use crate::data::format_bytes;
+use crate::data::process::{self, Processes, SortColumn, SIGNALS};
+use crate::sampler;
+use crossterm::event::KeyCode;
+use std::sync::Arc;
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+/// 键盘输入当前作用的对象
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub enum InputMode {
+    #[default]
+    Normal, // 快捷键
+    Filter, // 正在输入进程过滤条件
+    SignalMenu { pid: u32, selected: usize }, // 选择要发送的信号（SIGNALS 的下标）
+    ConfirmSignal { pid: u32, signal: usize }, // 确认发送信号
+    Renice { pid: u32, input: String },        // 输入新的 nice 值
+}
+
+/// 进程模块的界面状态
+#[derive(Default)]
+pub struct ProcessView {
+    pub input: InputMode,
+    pub read_only: bool, // 只读模式下禁止发送信号和 renice
+}
+
+impl ProcessView {
+    /// 处理一次按键，操作的结果写入 message；不是进程模块的按键时返回 false
+    fn handle_key(&mut self, processes: &mut Processes, code: KeyCode, message: &mut Option<String>) -> bool {
+        match &mut self.input {
+            // 输入过滤条件时，所有字符都写入过滤框
+            InputMode::Filter => match code {
+                KeyCode::Enter => self.input = InputMode::Normal,
+                KeyCode::Esc => {
+                    processes.filter.clear();
+                    processes.apply_view();
+                    self.input = InputMode::Normal;
+                }
+                KeyCode::Backspace => {
+                    processes.filter.pop();
+                    processes.apply_view();
+                }
+                KeyCode::Char(c) => {
+                    processes.filter.push(c);
+                    processes.apply_view();
+                }
+                _ => {}
+            },
+            InputMode::SignalMenu { pid, selected } => match code {
+                KeyCode::Up => *selected = selected.saturating_sub(1),
+                KeyCode::Down => *selected = (*selected + 1).min(SIGNALS.len() - 1),
+                KeyCode::Enter => self.input = InputMode::ConfirmSignal { pid: *pid, signal: *selected },
+                KeyCode::Esc => self.input = InputMode::Normal,
+                _ => {}
+            },
+            InputMode::ConfirmSignal { pid, signal } => {
+                if let KeyCode::Char('y') | KeyCode::Char('Y') = code {
+                    let (signal, name) = SIGNALS[*signal];
+                    *message = Some(match process::send_signal(*pid, signal) {
+                        Ok(()) => format!("Sent {} to {}", name, pid),
+                        Err(e) => e,
+                    });
+                }
+                // 除 y 以外的任何按键都视为取消
+                self.input = InputMode::Normal;
+            }
+            InputMode::Renice { pid, input } => match code {
+                KeyCode::Char(c) if c.is_ascii_digit() || (c == '-' && input.is_empty()) => input.push(c),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Enter => {
+                    *message = Some(match input.parse::<i32>() {
+                        Ok(nice) => match process::renice(*pid, nice) {
+                            Ok(()) => format!("Set nice value of {} to {}", pid, nice),
+                            Err(e) => e,
+                        },
+                        Err(_) => format!("Invalid nice value: {:?}", input),
+                    });
+                    self.input = InputMode::Normal;
+                }
+                KeyCode::Esc => self.input = InputMode::Normal,
+                _ => {}
+            },
+            // 快捷键：选择、排序、过滤、树形视图，以及对选中进程的操作
+            InputMode::Normal => match code {
+                KeyCode::Char('/') => self.input = InputMode::Filter,
+                KeyCode::Char('s') => processes.next_sort_column(),
+                KeyCode::Char('r') => processes.toggle_sort_order(),
+                KeyCode::Char('t') => processes.toggle_tree_mode(),
+                KeyCode::Left | KeyCode::Char('-') => processes.set_collapsed(Some(true)),
+                KeyCode::Right | KeyCode::Char('+') => processes.set_collapsed(Some(false)),
+                KeyCode::Char(' ') => processes.set_collapsed(None),
+                KeyCode::Up => processes.move_selection(-1),
+                KeyCode::Down => processes.move_selection(1),
+                KeyCode::PageUp => processes.move_selection(-10),
+                KeyCode::PageDown => processes.move_selection(10),
+                KeyCode::Home => processes.selected = 0,
+                KeyCode::End => processes.move_selection(isize::MAX),
+                // 对选中的进程操作
+                KeyCode::Char('k') | KeyCode::Char('n') if self.read_only => {
+                    *message = Some("Read-only mode: process actions are disabled".to_string());
+                }
+                KeyCode::Char('k') => {
+                    if let Some(entry) = processes.selected_entry() {
+                        self.input = InputMode::SignalMenu { pid: entry.pid, selected: 0 };
+                    }
+                }
+                KeyCode::Char('n') => {
+                    if let Some(entry) = processes.selected_entry() {
+                        self.input = InputMode::Renice { pid: entry.pid, input: String::new() };
+                    }
+                }
+                _ => return false,
+            },
+        }
+        true
+    }
+}
+
+/// 进程模块的按键；过滤框或弹出窗口打开时（Module::modal）收到所有按键
+pub fn on_key(app: &mut App, code: KeyCode) -> bool {
+    let system_info = Arc::clone(&app.system_info);
+    let mut info = sampler::lock(&system_info);
+    let mut message = None;
+    let handled = app.state_mut::<ProcessView>().handle_key(&mut info.processes, code, &mut message);
+    if message.is_some() {
+        app.status_message = message;
+    }
+    handled
+}
+
+/// 提示栏中的说明随输入状态和只读模式变化
+pub fn keys(app: &App) -> String {
+    let view = app.state::<ProcessView>();
+    if view.input == InputMode::Filter {
+        return String::from("Type to filter processes, Enter to confirm, Esc to clear.");
+    }
+    let mut keys = String::from(
+        "Processes: ↑/↓ select, '/' filter, 's' sort column, 'r' reverse order, 't' tree view, ←/→ collapse/expand",
+    );
+    if !view.read_only {
+        keys.push_str(", 'k' send signal, 'n' renice");
+    }
+    keys.push('.');
+    keys
+}
+
+/// 把 Unix 时间戳格式化为 "MM-DD HH:MM" (UTC)
+pub fn format_start_time(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let seconds = timestamp % 86_400;
+
+    // 由 1970-01-01 起的天数换算为公历日期（Howard Hinnant 的 civil_from_days 算法）
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+
+    format!("{:02}-{:02} {:02}:{:02}", month, day, seconds / 3600, seconds % 3600 / 60)
+}
+
+/// 进程表：表头高亮当前排序列，选中行反色显示
+pub fn draw_process_table(f: &mut Frame, area: Rect, processes: &Processes, editing_filter: bool) {
+    // 1. 标题中显示排序方式和过滤条件
+    let arrow = if processes.sort_desc { "▼" } else { "▲" };
+    let mut title = format!(
+        "{} ({}/{}) sort: {} {}",
+        if processes.tree_mode { "Process Tree" } else { "Processes" },
+        processes.visible.len(),
+        processes.entries.len(),
+        processes.sort_by.title(),
+        arrow
+    );
+    if editing_filter || !processes.filter.is_empty() {
+        title.push_str(&format!("  filter: /{}", processes.filter));
+        if editing_filter {
+            title.push('_'); // 模拟光标
+        }
+    }
+
+    // 2. 表头
+    let header = Row::new(SortColumn::ALL.iter().map(|&column| {
+        let style = if column == processes.sort_by {
+            Style::default().fg(Color::Black).bg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::Cyan)
+        };
+        // 树形模式下 CPU% 和 RSS 显示整棵子树的总和
+        let title = match column {
+            SortColumn::Cpu if processes.tree_mode => "ΣCPU%",
+            SortColumn::Rss if processes.tree_mode => "ΣRSS",
+            _ => column.title(),
+        };
+        Cell::from(title).style(style)
+    }))
+    .style(Style::default().add_modifier(Modifier::BOLD));
+
+    // 3. 只构造能显示出来的行，保证选中行始终可见
+    let height = area.height.saturating_sub(3) as usize; // 上下边框 + 表头
+    let offset = if height > 0 && processes.selected >= height {
+        processes.selected + 1 - height
+    } else {
+        0
+    };
+    let rows: Vec<Row> = processes
+        .visible
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(height)
+        .map(|(pos, &index)| {
+            let p = &processes.entries[index];
+            let (cpu, rss, command) = if processes.tree_mode {
+                let marker = if !p.has_children {
+                    "  "
+                } else if processes.is_collapsed(p.pid) {
+                    "▸ "
+                } else {
+                    "▾ "
+                };
+                let indent = "  ".repeat(p.depth);
+                (p.subtree_cpu, p.subtree_rss, format!("{}{}{}", indent, marker, p.command))
+            } else {
+                (p.cpu, p.rss, p.command.clone())
+            };
+            let row = Row::new(vec![
+                Cell::from(p.pid.to_string()),
+                Cell::from(p.user.clone()),
+                Cell::from(format!("{:.1}", cpu)),
+                Cell::from(format_bytes(rss)),
+                Cell::from(format_bytes(p.virtual_mem)),
+                Cell::from(p.state.clone()),
+                Cell::from(format_start_time(p.start_time)),
+                Cell::from(command),
+            ]);
+            if pos == processes.selected {
+                row.style(Style::default().add_modifier(Modifier::REVERSED))
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(8),
+        Constraint::Length(10),
+        Constraint::Length(6),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(9),
+        Constraint::Length(11),
+        Constraint::Min(10),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::default().title(title).borders(Borders::ALL));
+    f.render_widget(table, area);
+}
+
+/// 在 area 中央取出一个固定大小的矩形
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+/// 发送信号、确认和 renice 的弹出窗口；普通模式下不绘制任何内容
+pub fn draw_action_popup(f: &mut Frame, mode: &InputMode, processes: &Processes) {
+    let describe = |pid: u32| -> String {
+        let command = processes
+            .entries
+            .iter()
+            .find(|e| e.pid == pid)
+            .map(|e| e.command.as_str())
+            .unwrap_or("?");
+        let mut text = format!("{} {}", pid, command);
+        if text.chars().count() > 40 {
+            text = text.chars().take(39).collect::<String>() + "…";
+        }
+        text
+    };
+
+    let (title, lines, border) = match mode {
+        InputMode::SignalMenu { pid, selected } => {
+            let mut lines = vec![Line::from(describe(*pid)), Line::from("")];
+            for (i, (_, name)) in SIGNALS.iter().enumerate() {
+                let style = if i == *selected {
+                    Style::default().add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::default()
+                };
+                lines.push(Line::styled(format!("  {}  ", name), style));
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from("↑/↓ choose, Enter send, Esc cancel"));
+            ("Send Signal", lines, Color::Yellow)
+        }
+        InputMode::ConfirmSignal { pid, signal } => {
+            let lines = vec![
+                Line::from(format!("Send {} to", SIGNALS[*signal].1)),
+                Line::from(describe(*pid)),
+                Line::from(""),
+                Line::from("Press 'y' to confirm, any other key to cancel"),
+            ];
+            ("Confirm", lines, Color::Red)
+        }
+        InputMode::Renice { pid, input } => {
+            let lines = vec![
+                Line::from(describe(*pid)),
+                Line::from(""),
+                Line::from(format!("New nice value (-20..19): {}_", input)),
+                Line::from(""),
+                Line::from("Enter apply, Esc cancel"),
+            ];
+            ("Renice", lines, Color::Yellow)
+        }
+        InputMode::Normal | InputMode::Filter => return,
+    };
+
+    let area = centered_rect(50, lines.len() as u16 + 2, f.area());
+    let popup = Paragraph::new(lines).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border)),
+    );
+    f.render_widget(Clear, area); // 先清除弹窗下面的内容
+    f.render_widget(popup, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_start_time_in_utc() {
+        assert_eq!(format_start_time(0), "01-01 00:00");
+        // 2024-02-29 13:45:59 UTC，闰日
+        assert_eq!(format_start_time(1_709_214_359), "02-29 13:45");
+        // 2000-03-01 00:00:00 UTC，400 年闰年的次日
+        assert_eq!(format_start_time(951_868_800), "03-01 00:00");
+        // 2023-12-31 23:59:00 UTC
+        assert_eq!(format_start_time(1_704_067_140), "12-31 23:59");
+    }
+}